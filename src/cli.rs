@@ -1,4 +1,7 @@
+use crate::downloader::traits::{BundleType, ImageType};
+use crate::env::shell::Shell;
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(author, version, about = "A tool to manage and switch between JDK installations", long_about = None)]
@@ -17,23 +20,170 @@ pub enum Commands {
 
     /// Switch to a specific JDK version
     Use {
-        /// Version identifier (e.g., 8, 11, 17, 21)
+        /// Version spec or JDK key (e.g., 17, 17.0.9, >=17, lts, latest, temurin@21, temurin-17.0.9)
         version: String,
     },
+
+    /// Exit with an error unless the active JDK satisfies a version spec
+    Check {
+        /// Version spec (e.g., 17, >=17, lts, temurin@21)
+        spec: String,
+    },
+    
+    /// Pin a JDK version for the current directory (.java-version)
+    Local {
+        /// Version or JDK key (e.g., 17, 17.0.9, temurin-17.0.9); omit to show the project JDK
+        version: Option<String>,
+
+        /// Remove the .java-version file from the current directory
+        #[arg(long, conflicts_with = "version")]
+        unset: bool,
+    },
+    
+    /// Print shell code that activates a JDK (eval "$(jsh env 17)")
+    Env {
+        /// Version or JDK key; defaults to the JDK selected for the current directory
+        version: Option<String>,
+
+        /// Shell to generate code for (default: detected from $SHELL)
+        #[arg(long, value_enum)]
+        shell: Option<Shell>,
+
+        /// Roll back the last change jsh made to the env file and shell startup file
+        #[arg(long, conflicts_with_all = ["version", "shell"])]
+        restore: bool,
+    },
+
+    /// Print the shell integration snippet (eval "$(jsh init bash)")
+    Init {
+        /// Target shell
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+    
+    /// Regenerate shims for java, javac, jar and the other JDK tools
+    Rehash,
+
+    /// Run a JDK tool with the JDK selected for the current directory
+    // -h and --help belong to the tool (`javac -h <dir>`), `jsh help exec` still shows this help
+    #[command(disable_help_flag = true)]
+    Exec {
+        /// Tool name (e.g., java, javac, jar)
+        tool: String,
+
+        /// Arguments passed to the tool
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
     
     /// Download a specific JDK version
     Download {
-        /// Version to download (e.g., 17, 21)
+        /// Version spec to download (e.g., 17, 21, lts, latest, >=17, temurin@21)
         version: String,
 
-        /// JDK vendor (default: temurin)
-        #[arg(long, default_value = "temurin")]
-        vendor: String,
+        /// JDK vendor: temurin, zulu, corretto, graalvm, liberica, microsoft, sapmachine, semeru (default: temurin)
+        #[arg(long)]
+        vendor: Option<String>,
+
+        /// Package bundle: standard, full (with JavaFX) or lite; full and lite are Liberica only
+        #[arg(long, value_enum, default_value_t = BundleType::Standard)]
+        bundle: BundleType,
+
+        /// Image type: jdk, jre, debugimage or sources; images other than jdk are temurin, microsoft and semeru only
+        #[arg(long, value_enum, default_value_t = ImageType::Jdk)]
+        image: ImageType,
+
+        /// Download for another OS: linux, mac or windows (default: this machine's)
+        #[arg(long)]
+        os: Option<String>,
+
+        /// Download for another arch: x64, aarch64, ppc64le, s390x, riscv64 or arm (default: this machine's)
+        #[arg(long)]
+        arch: Option<String>,
+
+        /// Unpack into this directory instead of installing and registering the JDK, required for other platforms
+        #[arg(long, value_name = "DIR")]
+        dest: Option<PathBuf>,
+
+        /// Ignore cached release metadata and query the vendor again
+        #[arg(long)]
+        refresh: bool,
+    },
+
+    /// Install a JDK from a local archive (e.g., OpenJDK17U-jdk_x64_linux_hotspot_17.0.9_9.tar.gz)
+    Install {
+        /// Archive to install (.tar.gz, .tar.xz, .tar.zst, .tar or .zip)
+        #[arg(long, value_name = "ARCHIVE")]
+        from_file: PathBuf,
+
+        /// Expected SHA-256 checksum of the archive
+        #[arg(long)]
+        sha256: Option<String>,
+    },
+
+    /// Register an already extracted JDK directory
+    Add {
+        /// JDK home directory (the one containing bin/java)
+        path: PathBuf,
+    },
+    
+    /// Remove a JDK (deletes jsh-installed JDKs, unregisters others)
+    #[command(alias = "uninstall")]
+    Remove {
+        /// JDK key or version spec matching a single JDK (e.g., temurin-17.0.9, 17, zulu@11)
+        version: String,
+
+        /// Remove even if it is the active JDK
+        #[arg(long)]
+        force: bool,
     },
     
     /// Search available JDK versions for download
     Search {
         /// Optional search keyword
         keyword: Option<String>,
+
+        /// List every release of the major version given as keyword, not only the latest
+        #[arg(long, requires = "keyword")]
+        all: bool,
+
+        /// Only search this vendor (default: every vendor known to the foojay Disco API)
+        #[arg(long)]
+        vendor: Option<String>,
+
+        /// Only show packages of this bundle type
+        #[arg(long, value_enum)]
+        bundle: Option<BundleType>,
+
+        /// Search packages for another OS: linux, mac or windows
+        #[arg(long)]
+        os: Option<String>,
+
+        /// Search packages for another arch: x64, aarch64, ppc64le, s390x, riscv64 or arm
+        #[arg(long)]
+        arch: Option<String>,
+
+        /// Ignore cached release metadata and query the vendors again
+        #[arg(long)]
+        refresh: bool,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exec_passes_help_flags_to_tool() {
+        for args in [vec!["java", "--help"], vec!["javac", "-h", "/tmp/out", "Foo.java"]] {
+            let cli = Cli::try_parse_from(["jsh", "exec"].into_iter().chain(args.iter().copied())).unwrap();
+            match cli.command {
+                Commands::Exec { tool, args: tool_args } => {
+                    assert_eq!(tool, args[0]);
+                    assert_eq!(tool_args, args[1..]);
+                }
+                _ => panic!("expected exec"),
+            }
+        }
+    }
+}
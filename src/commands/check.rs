@@ -0,0 +1,58 @@
+use crate::config::JdkInfo;
+use crate::error::{JdkError, Result};
+use crate::jdk::JdkManager;
+use crate::jdk::detector::JdkDetector;
+use crate::jdk::resolver::{self, Origin};
+use crate::jdk::spec::JdkSpec;
+use colored::*;
+use std::path::PathBuf;
+
+/// succeed only when the active JDK satisfies `spec`, for CI scripts asserting their toolchain
+pub fn check_command(spec: &str) -> Result<()> {
+    let parsed = JdkSpec::parse(spec)?;
+    let manager = JdkManager::new()?;
+    let (info, source) = active_jdk(&manager)?;
+    let actual = info.java_version.clone().unwrap_or_else(|| info.version.clone());
+    let vendor = info.vendor.as_deref().unwrap_or("unknown vendor");
+
+    if !parsed.matches(&info) {
+        return Err(JdkError::UnsatisfiedVersion {
+            spec: spec.to_string(),
+            actual: format!("{} ({}, from {})", actual, vendor, source),
+        });
+    }
+    println!(
+        "{} JDK {} ({}) satisfies {}",
+        "[OK]".green(),
+        actual,
+        vendor,
+        spec.green()
+    );
+    Ok(())
+}
+
+/// same precedence as `jsh current`: shell override or project file, JAVA_HOME, then global config
+fn active_jdk(manager: &JdkManager) -> Result<(JdkInfo, String)> {
+    let cwd = std::env::current_dir()?;
+    if let Some(resolution) = resolver::resolve(manager, &cwd)?.filter(|r| r.origin != Origin::Global) {
+        return Ok((resolution.info, resolution.origin.to_string()));
+    }
+
+    if let Ok(java_home) = std::env::var("JAVA_HOME")
+        && !java_home.is_empty()
+    {
+        let path = PathBuf::from(&java_home);
+        // JDKs set up by other tools are checked too
+        let info = manager
+            .list_jdks()
+            .into_iter()
+            .find(|(_, info)| info.path == path)
+            .map(|(_, info)| info.clone())
+            .or_else(|| JdkDetector::get_jdk_info(&path))
+            .ok_or_else(|| JdkError::InvalidPath(java_home.clone()))?;
+        return Ok((info, "JAVA_HOME".to_string()));
+    }
+
+    let info = manager.get_current().ok_or(JdkError::NoActiveJdk)?;
+    Ok((info.clone(), "global config".to_string()))
+}
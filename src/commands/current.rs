@@ -1,13 +1,21 @@
 use crate::error::{JdkError, Result};
 use crate::jdk::JdkManager;
+use crate::jdk::resolver::{self, Origin};
 use colored::*;
 
 pub fn current_command() -> Result<()> {
     let manager = JdkManager::new()?;
     let java_home_env = std::env::var("JAVA_HOME").ok();
+    let cwd = std::env::current_dir()?;
 
-    // Priority 1: Check JAVA_HOME environment variable first
-    let (current, version_key, source) = if let Some(ref java_home) = java_home_env {
+    // Priority 1: shell override or project file
+    let resolution = resolver::resolve(&manager, &cwd)?
+        .filter(|r| r.origin != Origin::Global);
+
+    let (current, version_key, source) = if let Some(resolution) = resolution {
+        (resolution.info, resolution.key, resolution.origin.to_string())
+    } else if let Some(ref java_home) = java_home_env {
+        // Priority 2: Check JAVA_HOME environment variable
         let java_home_path = std::path::PathBuf::from(java_home);
         
         // Find JDK info by path from registered JDKs
@@ -16,7 +24,7 @@ pub fn current_command() -> Result<()> {
             .find(|(_, info)| info.path == java_home_path);
         
         if let Some((key, info)) = found {
-            (info, key.clone(), "environment variable")
+            (info.clone(), key.clone(), "environment variable".to_string())
         } else {
             // JAVA_HOME is set but not managed by jsh
             return Err(JdkError::ConfigError(format!(
@@ -25,11 +33,11 @@ pub fn current_command() -> Result<()> {
             )));
         }
     } else {
-        // Priority 2: Fall back to config file
+        // Priority 3: Fall back to config file
         let current = manager.get_current()
             .ok_or(JdkError::NoActiveJdk)?;
         let version_key = manager.get_current_version().unwrap().clone();
-        (current, version_key, "config file (JAVA_HOME not set)")
+        (current.clone(), version_key, "config file (JAVA_HOME not set)".to_string())
     };
 
     println!("{}", "Current JDK:".bold());
@@ -47,14 +55,16 @@ pub fn current_command() -> Result<()> {
     println!("{}", "=".repeat(60).bright_black());
 
     // Check environment status
-    if java_home_env.is_some() {
-        if source == "environment variable" {
-            println!("\n{} JAVA_HOME is correctly set", "[OK]".green());
-        } else {
-            println!("\n{} JAVA_HOME is not set in environment", "[!]".yellow());
-            println!("  The above JDK is from config file.");
-            println!("  {}", "Try restarting your terminal or running the use command again.".yellow());
-        }
+    let java_home_matches = java_home_env
+        .as_ref()
+        .map(|home| std::path::Path::new(home) == current.path)
+        .unwrap_or(false);
+
+    if java_home_matches {
+        println!("\n{} JAVA_HOME is correctly set", "[OK]".green());
+    } else if java_home_env.is_some() {
+        println!("\n{} JAVA_HOME points to a different JDK", "[!]".yellow());
+        println!("  The above JDK was selected by {}.", source);
     } else {
         println!("\n{} JAVA_HOME is not set in environment", "[!]".yellow());
     }
@@ -1,73 +1,264 @@
 use colored::Colorize;
 use crate::config::Config;
-use crate::downloader::adoptium::AdoptiumSource;
 use crate::downloader::downloader::Downloader;
 use crate::downloader::extractor::Extractor;
 use crate::downloader::progress::ProgressDisplay;
-use crate::downloader::traits::JdkSource;
+use crate::downloader::source_for_vendor;
+use crate::downloader::traits::{BundleType, ImageType, JdkPackage, detect_arch, detect_os, is_cross_target};
 use crate::error::{JdkError, Result};
 use crate::jdk::JdkManager;
+use crate::jdk::detector::JdkDetector;
+use crate::jdk::shims;
+use crate::jdk::spec::{JdkSpec, normalize_vendor};
+use std::fs;
+use std::path::{Path, PathBuf};
 
-pub async fn download_command(version: &str, vendor: &str) -> Result<()> {
+pub async fn download_command(
+    version: &str,
+    vendor: Option<&str>,
+    bundle: BundleType,
+    image: ImageType,
+    dest: Option<&Path>,
+) -> Result<()> {
+    let spec = JdkSpec::parse(version)?;
+    // "temurin@21" names the vendor in the spec itself
+    let vendor = match (spec.vendor.as_deref(), vendor) {
+        (Some(from_spec), Some(flag)) if normalize_vendor(flag) != from_spec => {
+            return Err(JdkError::InvalidVersion(format!("{} conflicts with --vendor {}", version, flag)));
+        }
+        (Some(from_spec), _) => from_spec,
+        (None, Some(flag)) => flag,
+        (None, None) => "temurin",
+    };
     println!("  Version: {}", version);
     println!("  Vendor: {}", vendor);
+    if bundle != BundleType::Standard {
+        println!("  Bundle: {}", bundle);
+    }
+    if image != ImageType::Jdk {
+        println!("  Image: {}", image);
+    }
+    if is_cross_target() {
+        // java of another platform cannot run here to be registered
+        if dest.is_none() {
+            return Err(JdkError::UnsupportedPlatform(format!(
+                "{} ({}) is not this machine, pass --dest to unpack it into a directory",
+                detect_os(),
+                detect_arch()
+            )));
+        }
+        println!("  Platform: {} ({})", detect_os(), detect_arch());
+    }
     println!("{}", format!("Searching for JDK {}...", version).cyan());
 
-    let source: Box<dyn JdkSource> = match vendor.to_lowercase().as_str() {
-        "temurin" | "adoptium" => Box::new(AdoptiumSource::new()),
-        _ => {
-            println!("{}", format!("Unknown vendor '{}', using Adoptium/Temurin", vendor).yellow());
-            Box::new(AdoptiumSource::new())
-        }
-    };
+    let source = source_for_vendor(vendor)?;
 
-    let version_num: u32 = version.parse()
-        .map_err(|_| JdkError::InvalidVersion(version.to_string()))?;
+    let major = match spec.major() {
+        Some(major) => major,
+        // ">=17", "lts" and "latest" depend on what the vendor publishes
+        None => source
+            .fetch_version()
+            .await?
+            .iter()
+            .filter(|p| spec.matches_package(p))
+            .map(|p| p.major_version)
+            .max()
+            .ok_or_else(|| JdkError::PackageNotFound(spec.to_string()))?,
+    };
 
-    let package = source.find_package(version_num).await?;
+    let package = if spec.names_release() {
+        // "17.0.5+8" needs the release history, not only the latest build
+        source
+            .list_releases(major, image)
+            .await?
+            .into_iter()
+            .filter(|p| p.bundle_type == bundle)
+            .find(|p| spec.matches_package(p))
+            .ok_or_else(|| JdkError::PackageNotFound(format!("{} from {}", spec, source.name())))?
+    } else if image == ImageType::Jdk {
+        source.find_bundle(major, bundle).await?
+    } else {
+        // bundles other than standard only exist as JDKs
+        Some(source.find_image(major, image).await?)
+            .filter(|p| p.bundle_type == bundle)
+            .ok_or_else(|| JdkError::PackageNotFound(format!("{} ({} bundle, {} image)", major, bundle, image)))?
+    };
     println!("\n{}", "Found package:".green().bold());
     println!("  Version:     {}", package.version);
     println!("  Vendor:      {}", package.vendor);
     println!("  Size:        {} MB", package.size / 1024 / 1024);
     println!("  Platform:    {} ({})", package.os, package.arch);
     println!("  File type:   {}", package.file_type);
+    if package.bundle_type != BundleType::Standard {
+        println!("  Bundle:      {}", package.bundle_type);
+    }
+    if package.image_type != ImageType::Jdk {
+        println!("  Image:       {}", package.image_type);
+    }
     if package.is_lts {
         println!("  Support:     {}", "LTS (Long Term Support)".green());
     }
 
+    if dest.is_none() && image.is_runtime() && let Some(keys) = installed_keys(&package, image)? {
+        println!("\n{} {}", "[OK]".green(), format!("Already installed as: {}", keys).green());
+        let key = keys.split(", ").next().unwrap_or(&keys);
+        println!("  Activate it with: {}", format!("jsh use {}", key).cyan());
+        return Ok(());
+    }
+
+    if package.checksum.is_none() {
+        println!("{}", "Warning: No checksum published for this package, skipping verification".yellow());
+    }
+
     println!("\n{}", "Downloading...".cyan());
     let downloader = Downloader::new()?;
-    let filename = format!("jdk-{}-{}.{}",
-                           package.major_version,
-                           package.vendor,
-                           package.file_type
-    );
+    // named after the full version, a cached 17.0.9 archive must not be taken for 17.0.5
+    // and after the platform, `--os`/`--arch` downloads share the cache with native ones
+    let filename = match package.bundle_type {
+        BundleType::Standard => format!("{}-{}-{}-{}-{}.{}",
+                                        package.image_type,
+                                        package.version,
+                                        package.vendor,
+                                        detect_os(),
+                                        detect_arch(),
+                                        package.file_type
+        ),
+        bundle => format!("{}-{}-{}-{}-{}-{}.{}",
+                          package.image_type,
+                          package.version,
+                          package.vendor,
+                          detect_os(),
+                          detect_arch(),
+                          bundle,
+                          package.file_type
+        ),
+    };
 
     let archive_path = downloader.download_file(
         &package.download_url,
         &filename,
+        package.checksum.as_deref().map(|c| (c, package.checksum_type)),
+        // some sources cannot tell the size up front
+        (package.size > 0).then_some(package.size),
         ProgressDisplay::simple_callback()
     ).await?;
 
     println!("{}", "[OK] Download complete".green());
+    if package.checksum.is_some() {
+        println!("{}", format!("[OK] {} checksum verified", package.checksum_type).green());
+    }
+    if let Some(dest) = dest {
+        return unpack_to(&archive_path, dest);
+    }
+    if !image.is_runtime() {
+        return attach_to_jdk(&package, &archive_path);
+    }
     println!("\n{}", "Extracting...".cyan());
-    let extractor = Extractor::new();
-    let install_base = Config::config_dir()?.join("jdks");
-    let jdk_path = extractor.extract(&archive_path, &install_base)?;
+    // staged, so a JDK already in the jdks dir is not taken for the new one
+    let jdk_path = Extractor::new().install(&archive_path, &Config::jdks_dir()?)?;
     println!("{}", format!("[OK] Extracted to: {}", jdk_path.display()).green());
 
     println!("\n{}", "Registering JDK...".cyan());
     let mut manager = JdkManager::new()?;
     manager.scan_jdks()?;
+    let info = JdkDetector::get_jdk_info(&jdk_path)
+        .ok_or_else(|| JdkError::InvalidPath(jdk_path.display().to_string()))?;
+    let key = manager.register(info);
+    manager.save()?;
+    shims::refresh_if_enabled(&manager)?;
+    println!("{}", format!("[OK] Registered as: {}", key).green());
 
     if let Err(e) = std::fs::remove_file(&archive_path) {
         println!("{}", format!("Warning: Failed to remove archive: {}", e).yellow());
     }
 
-    println!("\n{} {}", "[SUCCESS]".green().bold(), "JDK installed successfully!".green());
+    let installed = if image == ImageType::Jre { "JRE installed successfully!" } else { "JDK installed successfully!" };
+    println!("\n{} {}", "[SUCCESS]".green().bold(), installed.green());
     println!("\n{}", "Next steps:".bold());
     println!("  1. List all JDKs:    {}", "jsh list".cyan());
-    println!("  2. Activate this JDK: {}", format!("jsh use {}", version).cyan());
+    println!("  2. Activate this JDK: {}", format!("jsh use {}", key).cyan());
+
+    Ok(())
+}
+
+/// Registry keys of installed copies of this release, checked before downloading it again.
+/// JDKs in the jdks dir that the registry lost are registered first
+fn installed_keys(package: &JdkPackage, image: ImageType) -> Result<Option<String>> {
+    let mut manager = JdkManager::new()?;
+    for info in JdkDetector::scan_directory(&Config::jdks_dir()?)? {
+        manager.register(info);
+    }
+    manager.save()?;
+    shims::refresh_if_enabled(&manager)?;
+    match manager.resolve_release(package, image == ImageType::Jre) {
+        Ok(key) => Ok(Some(key)),
+        Err(JdkError::AmbiguousVersion { candidates, .. }) => Ok(Some(candidates)),
+        Err(JdkError::JdkNotFound(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
 
+/// `--dest`: unpack the archive as it is, nothing is registered
+fn unpack_to(archive_path: &Path, dest: &Path) -> Result<()> {
+    println!("\n{}", "Extracting...".cyan());
+    Extractor::new().unpack(archive_path, dest)?;
+    println!("{}", format!("[OK] Extracted to: {}", dest.display()).green());
+
+    if let Err(e) = fs::remove_file(archive_path) {
+        println!("{}", format!("Warning: Failed to remove archive: {}", e).yellow());
+    }
+    println!("\n{} {}", "[SUCCESS]".green().bold(), "Package unpacked, it is not registered with jsh".green());
     Ok(())
 }
+
+/// Debug images and sources are not Java homes, unpack them into the installed JDK of the same release
+/// (eg: `<jdk>/debugimage`, `<jdk>/sources`)
+fn attach_to_jdk(package: &JdkPackage, archive_path: &Path) -> Result<()> {
+    let mut manager = JdkManager::new()?;
+    manager.scan_jdks()?;
+    let key = match manager.resolve_release(package, false) {
+        Err(JdkError::JdkNotFound(release)) => {
+            return Err(JdkError::UnresolvedVersion {
+                version: release,
+                origin: format!("the {} image", package.image_type),
+            });
+        }
+        result => result?,
+    };
+    let jdk_path = manager.config().get_jdk(&key).unwrap().path.clone();
+
+    println!("\n{}", "Extracting...".cyan());
+    let target = jdk_path.join(package.image_type.to_string());
+    let staging = jdk_path.join(format!(".{}-{}", package.image_type, std::process::id()));
+    let result = Extractor::new()
+        .unpack(archive_path, &staging)
+        .and_then(|_| replace_dir(&single_child(&staging), &target));
+    fs::remove_dir_all(&staging).ok();
+    result?;
+    println!("{}", format!("[OK] Extracted to: {}", target.display()).green());
+
+    if let Err(e) = fs::remove_file(archive_path) {
+        println!("{}", format!("Warning: Failed to remove archive: {}", e).yellow());
+    }
+    println!("\n{} {}", "[SUCCESS]".green().bold(), format!("Added {} to JDK {}", package.image_type, key).green());
+    Ok(())
+}
+
+/// archives usually wrap everything in one top-level dir, which is not worth keeping
+fn single_child(dir: &Path) -> PathBuf {
+    let entries: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).collect())
+        .unwrap_or_default();
+    match entries.as_slice() {
+        [only] if only.is_dir() => only.clone(),
+        _ => dir.to_path_buf(),
+    }
+}
+
+/// move `from` to `to`, replacing what an earlier download left there
+fn replace_dir(from: &Path, to: &Path) -> Result<()> {
+    if to.exists() {
+        fs::remove_dir_all(to).map_err(JdkError::IoError)?;
+    }
+    fs::rename(from, to).map_err(JdkError::IoError)
+}
@@ -0,0 +1,70 @@
+use crate::env::shell::{self, Shell};
+use crate::error::{JdkError, Result};
+use crate::jdk::JdkManager;
+use crate::jdk::resolver;
+use colored::*;
+use std::path::PathBuf;
+
+/// Print shell code that activates a JDK, meant for `eval "$(jsh env 17)"`
+pub fn env_command(shell: Option<Shell>, version: Option<&str>) -> Result<()> {
+    let shell = shell.unwrap_or_else(Shell::detect);
+    let manager = JdkManager::new()?;
+
+    let jdk = match version {
+        Some(version) => {
+            let key = manager.resolve_key(version)
+                .ok_or_else(|| JdkError::JdkNotFound(version.to_string()))?;
+            manager.config().get_jdk(&key).unwrap().clone()
+        }
+        None => {
+            let cwd = std::env::current_dir()?;
+            resolver::resolve(&manager, &cwd)?
+                .ok_or(JdkError::NoActiveJdk)?
+                .info
+        }
+    };
+
+    // drop bin dirs of every other known JDK so repeated evals don't pile up
+    let mut stale_bins: Vec<PathBuf> = manager.list_jdks()
+        .iter()
+        .map(|(_, info)| info.path.join("bin"))
+        .collect();
+    if let Ok(java_home) = std::env::var("JAVA_HOME") {
+        stale_bins.push(PathBuf::from(java_home).join("bin"));
+    }
+
+    let current_path = std::env::var("PATH").unwrap_or_default();
+    let path = shell::build_path(&jdk.path, &current_path, &stale_bins);
+    print!("{}", shell.render_env(&jdk.path, &path));
+
+    Ok(())
+}
+
+/// Print the shell function that applies `jsh use` to the running session
+pub fn init_command(shell: Shell) -> Result<()> {
+    print!("{}", shell.init_script());
+    Ok(())
+}
+
+/// Roll back the last change `jsh use` made to the shell startup files
+pub fn restore_command() -> Result<()> {
+    #[cfg(not(target_os = "windows"))]
+    {
+        match crate::env::unix::UnixEnvUpdater::restore()? {
+            Some(files) => {
+                println!("{} {}", "[OK]".green().bold(), "Restored the previous environment".green());
+                for file in files {
+                    println!("  {}", file.display());
+                }
+            }
+            None => println!("{}", "No backups to restore.".yellow()),
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        println!("{}", "Nothing to restore: jsh does not write shell startup files on Windows.".yellow());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use crate::config::Config;
+use crate::downloader::downloader::verify_file_checksum;
+use crate::downloader::extractor::Extractor;
+use crate::downloader::traits::ChecksumType;
+use crate::error::{JdkError, Result};
+use crate::jdk::JdkManager;
+use crate::jdk::detector::JdkDetector;
+use crate::jdk::shims;
+use colored::*;
+use std::fs;
+use std::path::Path;
+
+/// install a JDK from a local archive, for machines that cannot reach any download source
+pub fn install_command(archive: &Path, sha256: Option<&str>) -> Result<()> {
+    if !archive.is_file() {
+        return Err(JdkError::InvalidPath(archive.display().to_string()));
+    }
+    println!("{}", format!("Installing JDK from {}...", archive.display()).cyan());
+
+    match sha256 {
+        Some(expected) => {
+            verify_file_checksum(archive, expected, ChecksumType::Sha256)?;
+            println!("{}", "[OK] SHA-256 checksum verified".green());
+        }
+        None => println!("{}", "Warning: No --sha256 given, skipping verification".yellow()),
+    }
+
+    println!("\n{}", "Extracting...".cyan());
+    let jdk_path = Extractor::new().install(archive, &Config::jdks_dir()?)?;
+    println!("{}", format!("[OK] Extracted to: {}", jdk_path.display()).green());
+
+    let key = register(&jdk_path)?;
+    println!("{}", format!("[OK] Registered as: {}", key).green());
+
+    println!("\n{} {}", "[SUCCESS]".green().bold(), "JDK installed successfully!".green());
+    println!("  Activate it with: {}", format!("jsh use {}", key).cyan());
+    Ok(())
+}
+
+/// register an already extracted JDK, its files stay where they are
+pub fn add_command(path: &Path) -> Result<()> {
+    let path = fs::canonicalize(path)
+        .map_err(|_| JdkError::InvalidPath(path.display().to_string()))?;
+    // macOS bundles keep the JDK in Contents/Home
+    let bundle_home = path.join("Contents").join("Home");
+    let path = if !JdkDetector::is_valid_runtime(&path) && JdkDetector::is_valid_runtime(&bundle_home) {
+        bundle_home
+    } else {
+        path
+    };
+    if !JdkDetector::is_valid_runtime(&path) {
+        return Err(JdkError::InvalidPath(format!(
+            "{} is not a JDK (bin/java or lib not found)",
+            path.display()
+        )));
+    }
+
+    let key = register(&path)?;
+    println!("{}", format!("[OK] Registered {} as: {}", path.display(), key).green());
+    println!("  Activate it with: {}", format!("jsh use {}", key).cyan());
+    Ok(())
+}
+
+/// register a JDK directory and refresh shims, returns its registry key
+fn register(path: &Path) -> Result<String> {
+    let info = JdkDetector::get_jdk_info(path)
+        .ok_or_else(|| JdkError::InvalidPath(path.display().to_string()))?;
+    let mut manager = JdkManager::new()?;
+    let key = manager.register(info);
+    manager.save()?;
+    shims::refresh_if_enabled(&manager)?;
+    Ok(key)
+}
@@ -1,5 +1,6 @@
 use crate::error::Result;
 use crate::jdk::JdkManager;
+use crate::jdk::shims;
 use colored::*;
 
 pub fn list_command() -> Result<()> {
@@ -7,6 +8,7 @@ pub fn list_command() -> Result<()> {
     
     println!("{}", "Scanning for JDK installations...".cyan());
     manager.scan_jdks()?;
+    shims::refresh_if_enabled(&manager)?;
     
     let config_recently_modified = crate::config::Config::config_path()
         .ok()
@@ -16,28 +18,27 @@ pub fn list_command() -> Result<()> {
         .map(|e| e.as_secs() < 10)
         .unwrap_or(false);
     
-    let java_home_path = std::env::var("JAVA_HOME").ok().map(|s| std::path::PathBuf::from(s));
+    let java_home_path = std::env::var("JAVA_HOME").ok().map(std::path::PathBuf::from);
     
-    if !config_recently_modified {
-        if let Some(ref home_path) = java_home_path {
-            
-            let system_jdk_version = manager.config().jdks.iter()
-                .find(|(_, info)| &info.path == home_path)
-                .map(|(key, _)| key.clone());
-            
-            let config_current = manager.get_current_version();
-            let needs_update = match (config_current, &system_jdk_version) {
-                (None, Some(_)) => true,
-                (Some(config_ver), Some(system_ver)) => config_ver != system_ver,
-                _ => false,
-            };
-            
-            if needs_update {
-                if let Some(version) = system_jdk_version {
-                    manager.config_mut().set_current(version);
-                    manager.save()?;
-                }
-            }
+    if !config_recently_modified
+        && let Some(ref home_path) = java_home_path
+    {
+        let system_jdk_version = manager.config().jdks.iter()
+            .find(|(_, info)| &info.path == home_path)
+            .map(|(key, _)| key.clone());
+        
+        let config_current = manager.get_current_version();
+        let needs_update = match (config_current, &system_jdk_version) {
+            (None, Some(_)) => true,
+            (Some(config_ver), Some(system_ver)) => config_ver != system_ver,
+            _ => false,
+        };
+        
+        if needs_update
+            && let Some(version) = system_jdk_version
+        {
+            manager.config_mut().set_current(version);
+            manager.save()?;
         }
     }
     
@@ -93,6 +94,23 @@ pub fn list_command() -> Result<()> {
         if let Some(vendor) = &info.vendor {
             println!("  {} {}", "Vendor:".bright_black(), vendor);
         }
+
+        if let Some(arch) = &info.arch {
+            println!("  {} {}", "Arch:".bright_black(), arch);
+        }
+
+        if let Some(jvm_impl) = &info.jvm_impl {
+            let jvm_impl = if jvm_impl == "hotspot" { jvm_impl.normal() } else { jvm_impl.cyan() };
+            println!("  {} {}", "JVM:".bright_black(), jvm_impl);
+        }
+
+        if info.is_jre() {
+            println!("  {} {}", "Image:".bright_black(), "JRE (no javac)".yellow());
+        }
+
+        if info.native_image {
+            println!("  {} {}", "Tools:".bright_black(), "native-image".green());
+        }
         
         println!("  {} {}", "Path:".bright_black(), info.path.display());
         println!();
@@ -0,0 +1,49 @@
+use crate::error::{JdkError, Result};
+use crate::jdk::JdkManager;
+use crate::jdk::resolver::{self, VERSION_FILES};
+use crate::jdk::spec::JdkSpec;
+use colored::*;
+
+pub fn local_command(version: Option<&str>, unset: bool) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let version_file = cwd.join(VERSION_FILES[0]);
+
+    if unset {
+        if version_file.exists() {
+            std::fs::remove_file(&version_file)?;
+            println!("{}", format!("[OK] Removed {}", version_file.display()).green());
+        } else {
+            println!("{}", format!("No {} in this directory.", VERSION_FILES[0]).yellow());
+        }
+        return Ok(());
+    }
+
+    let Some(version) = version else {
+        match resolver::find_version_file(&cwd) {
+            Some(file) => {
+                println!("{} {}", "Project JDK:".bold(), file.version.green());
+                println!("{} {}", "File:".bright_black(), file.path.display());
+            }
+            None => {
+                println!("{}", "No project JDK set for this directory.".yellow());
+                println!("Use {} to pin one.", "jsh local <version>".green());
+            }
+        }
+        return Ok(());
+    };
+
+    let manager = JdkManager::new()?;
+    if manager.config().get_jdk(version).is_none() {
+        JdkSpec::parse(version)?;
+    }
+    let key = manager.resolve_key(version)
+        .ok_or_else(|| JdkError::JdkNotFound(version.to_string()))?;
+
+    std::fs::write(&version_file, format!("{}\n", version))?;
+
+    println!("{}", format!("[OK] Wrote {}", version_file.display()).green());
+    println!("  {} {}", "Version:".bright_black(), version.green());
+    println!("  {} JDK {}", "Resolves to:".bright_black(), key);
+
+    Ok(())
+}
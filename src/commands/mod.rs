@@ -1,11 +1,23 @@
 pub mod list;
 pub mod current;
+pub mod check;
 pub mod switch;
 pub mod search;
 pub mod download;
+pub mod install;
+pub mod remove;
+pub mod local;
+pub mod env;
+pub mod shims;
 
 pub use list::list_command;
 pub use current::current_command;
+pub use check::check_command;
 pub use switch::use_command;
 pub use search::search_command;
 pub use download::download_command;
+pub use install::{add_command, install_command};
+pub use remove::remove_command;
+pub use local::local_command;
+pub use env::{env_command, init_command, restore_command};
+pub use shims::{exec_command, rehash_command};
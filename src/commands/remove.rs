@@ -0,0 +1,166 @@
+use crate::config::{Config, JdkInfo};
+use crate::error::{JdkError, Result};
+use crate::jdk::JdkManager;
+use crate::jdk::shims;
+use colored::*;
+use std::path::{Component, Path, PathBuf};
+
+pub fn remove_command(version: &str, force: bool) -> Result<()> {
+    let mut manager = JdkManager::new()?;
+
+    let key = manager.resolve_unique_key(version)?;
+    let info = manager.config().get_jdk(&key).unwrap().clone();
+
+    let java_home = std::env::var_os("JAVA_HOME").map(PathBuf::from);
+    if ensure_removable(&manager, &key, &info, java_home.as_deref(), force)? {
+        println!("{}", format!("Warning: JDK {} is currently active", key).yellow());
+        println!("  Run {} afterwards to activate another JDK.", "jsh use <version>".green());
+    }
+
+    println!("{}", format!("Removing JDK {}...", key).cyan());
+    println!("  {} {}", "Path:".bright_black(), info.path.display());
+
+    let deleted = remove_jdk(&mut manager, &key, &Config::jdks_dir()?)?;
+    if let Some(install_dir) = &deleted {
+        println!("{}", format!("[OK] Deleted {}", install_dir.display()).green());
+    }
+
+    manager.save()?;
+    shims::refresh_if_enabled(&manager)?;
+
+    if deleted.is_some() {
+        println!("\n{} {}", "[SUCCESS]".green().bold(), "JDK removed successfully!".green());
+    } else {
+        println!("{}", "[OK] Unregistered from jsh".green());
+        println!("\n{}", "Note:".yellow().bold());
+        println!("  This JDK was not installed by jsh, its files were left in place.");
+    }
+
+    Ok(())
+}
+
+/// refuse to remove the active JDK (config or JAVA_HOME) unless forced, returns whether it is active
+fn ensure_removable(
+    manager: &JdkManager,
+    key: &str,
+    info: &JdkInfo,
+    java_home: Option<&Path>,
+    force: bool,
+) -> Result<bool> {
+    let active = manager.get_current_version().map(String::as_str) == Some(key)
+        || java_home == Some(info.path.as_path());
+    if active && !force {
+        return Err(JdkError::JdkInUse(key.to_string()));
+    }
+    Ok(active)
+}
+
+/// unregister a JDK, deleting its files only when it lives under `jdks_dir`.
+/// returns the deleted directory
+fn remove_jdk(manager: &mut JdkManager, key: &str, jdks_dir: &Path) -> Result<Option<PathBuf>> {
+    let info = manager
+        .config()
+        .get_jdk(key)
+        .cloned()
+        .ok_or_else(|| JdkError::JdkNotFound(key.to_string()))?;
+
+    let install_dir = install_root(&info.path, jdks_dir);
+    if let Some(dir) = &install_dir {
+        std::fs::remove_dir_all(dir)?;
+    }
+    manager.unregister(key);
+    Ok(install_dir)
+}
+
+/// top-level directory under the jdks dir that holds this JDK (eg: jdks/jdk-17.0.9+9),
+/// None for paths outside of it
+fn install_root(jdk_path: &Path, jdks_dir: &Path) -> Option<PathBuf> {
+    match jdk_path.strip_prefix(jdks_dir).ok()?.components().next()? {
+        Component::Normal(first) => Some(jdks_dir.join(first)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jdk(path: &Path) -> JdkInfo {
+        JdkInfo {
+            path: path.to_path_buf(),
+            version: "17".to_string(),
+            vendor: Some("Eclipse Temurin".to_string()),
+            java_version: Some("17.0.9".to_string()),
+            arch: Some("x64".to_string()),
+            native_image: false,
+            jvm_impl: None,
+            image_type: None,
+        }
+    }
+
+    #[test]
+    fn test_install_root() {
+        let jdks = Path::new("/data/jsh/jdks");
+        assert_eq!(
+            install_root(&jdks.join("jdk-17.0.9+9/Contents/Home"), jdks),
+            Some(jdks.join("jdk-17.0.9+9"))
+        );
+        assert_eq!(install_root(&jdks.join("jdk-21"), jdks), Some(jdks.join("jdk-21")));
+        assert_eq!(install_root(jdks, jdks), None);
+        assert_eq!(install_root(&jdks.join("../downloads"), jdks), None);
+        assert_eq!(install_root(Path::new("/data/jsh/jdks-old/jdk-17"), jdks), None);
+        assert_eq!(install_root(Path::new("/usr/lib/jvm/java-17"), jdks), None);
+    }
+
+    #[test]
+    fn test_refuses_active_jdk_without_force() {
+        let info = jdk(Path::new("/usr/lib/jvm/java-17"));
+        let mut manager = JdkManager::with_config(Config::default());
+        let key = manager.register(info.clone());
+
+        assert!(!ensure_removable(&manager, &key, &info, None, false).unwrap());
+
+        manager.config_mut().current_jdk = Some(key.clone());
+        assert!(matches!(
+            ensure_removable(&manager, &key, &info, None, false),
+            Err(JdkError::JdkInUse(k)) if k == key
+        ));
+        assert!(ensure_removable(&manager, &key, &info, None, true).unwrap());
+
+        manager.config_mut().current_jdk = None;
+        assert!(matches!(
+            ensure_removable(&manager, &key, &info, Some(&info.path), false),
+            Err(JdkError::JdkInUse(_))
+        ));
+        assert!(ensure_removable(&manager, &key, &info, Some(&info.path), true).unwrap());
+    }
+
+    #[test]
+    fn test_remove_jdk_deletes_only_installed_jdks() {
+        let base = std::env::temp_dir().join(format!("jsh-remove-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&base);
+        let jdks_dir = base.join("jdks");
+        let installed = jdks_dir.join("jdk-17.0.9+9/Contents/Home");
+        let external = base.join("external/jdk-21");
+        std::fs::create_dir_all(installed.join("bin")).unwrap();
+        std::fs::create_dir_all(external.join("bin")).unwrap();
+
+        let mut manager = JdkManager::with_config(Config::default());
+        let installed_key = manager.register(jdk(&installed));
+        let mut external_info = jdk(&external);
+        external_info.java_version = Some("21.0.1".to_string());
+        let external_key = manager.register(external_info);
+
+        let deleted = remove_jdk(&mut manager, &installed_key, &jdks_dir).unwrap();
+        assert_eq!(deleted, Some(jdks_dir.join("jdk-17.0.9+9")));
+        assert!(!jdks_dir.join("jdk-17.0.9+9").exists());
+        assert!(jdks_dir.exists());
+
+        assert_eq!(remove_jdk(&mut manager, &external_key, &jdks_dir).unwrap(), None);
+        assert!(external.join("bin").exists());
+        assert!(manager.config().get_jdk(&external_key).is_none());
+        assert!(manager.config().ignored.contains(&external));
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+}
@@ -1,14 +1,42 @@
-use crate::downloader::adoptium::AdoptiumSource;
-use crate::downloader::traits::JdkSource;
-use crate::error::Result;
+use crate::downloader::disco::DiscoSource;
+use crate::downloader::source_for_vendor;
+use crate::downloader::traits::{BundleType, ImageType, JdkPackage, JdkSource, detect_arch, detect_os, is_cross_target};
+use crate::error::{JdkError, Result};
+use crate::jdk::spec::JdkSpec;
 use colored::Colorize;
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
 
-pub async fn search_command(keyword: Option<String>) -> Result<()> {
+pub async fn search_command(
+    keyword: Option<String>,
+    vendor: Option<&str>,
+    bundle: Option<BundleType>,
+    all: bool,
+) -> Result<()> {
     println!("{}", "Searching for available JDK versions...".cyan());
+    if is_cross_target() {
+        println!("  Platform: {} ({})", detect_os(), detect_arch());
+    }
 
-    let source = AdoptiumSource::new();
-    let mut packages = source.fetch_version().await?;
+    // every vendor at once through the Disco API unless one is asked for
+    let source: Box<dyn JdkSource> = match vendor {
+        Some(vendor) => source_for_vendor(vendor)?,
+        None => Box::new(DiscoSource::new()),
+    };
+    let mut packages = if all {
+        // every patch release of the requested major version
+        let keyword = keyword.as_deref().unwrap_or_default();
+        let major = JdkSpec::parse(keyword)
+            .ok()
+            .and_then(|spec| spec.major())
+            .ok_or_else(|| JdkError::InvalidVersion(keyword.to_string()))?;
+        source.list_releases(major, ImageType::Jdk).await?
+    } else {
+        source.fetch_version().await?
+    };
+    if let Some(bundle) = bundle {
+        packages.retain(|p| p.bundle_type == bundle);
+    }
 
     println!(
         "{}",
@@ -39,45 +67,53 @@ pub async fn search_command(keyword: Option<String>) -> Result<()> {
         }
     }
 
-    let mut grouped: HashMap<u32, Vec<_>> = HashMap::new();
+    let total = packages.len();
+    // group by major version, then vendor
+    let mut grouped: BTreeMap<Reverse<u32>, BTreeMap<String, Vec<JdkPackage>>> = BTreeMap::new();
     for pkg in packages {
         grouped
-            .entry(pkg.major_version)
-            .or_insert_with(Vec::new)
+            .entry(Reverse(pkg.major_version))
+            .or_default()
+            .entry(pkg.vendor.clone())
+            .or_default()
             .push(pkg);
     }
     println!("\n{}", "Available JDK versions:".bold());
     println!("{}", "=".repeat(80).bright_black());
+    println!("  {:<7} {:<14} {:<18} {:>8}  {:<5} {}",
+             "Major".bold(), "Vendor".bold(), "Version".bold(), "Size".bold(), "LTS".bold(), "Notes".bold());
+    println!("{}", "-".repeat(80).bright_black());
 
-    let mut versions: Vec<_> = grouped.keys().collect();
-    versions.sort_by(|a, b| b.cmp(a));
-
-    for &version in &versions  {
-        let pkgs = &grouped[version];
-        let is_lts = pkgs[0].is_lts;
-        // version number title
-        let version_text = format!("JDK {}", version);
-        let lts_tag = if is_lts {
-            "(LTS)".green().bold()
-        } else {
-            "".normal()
-        };
-        println!("\n  {}{}", version_text.bold().cyan(), lts_tag);
-
-        for pkg in pkgs {
-            let size_mb = pkg.size / 1024 / 1024;
-            println!("    └─ {:8} {} {:>4}",
-                     pkg.vendor.bright_black(),
-                     pkg.version.white(),
-                     format!("[{} MB]", size_mb).bright_black()
-            );
+    for (Reverse(version), vendors) in &grouped {
+        let mut major_text = format!("JDK {}", version);
+        for pkgs in vendors.values() {
+            for pkg in pkgs {
+                let mut notes = Vec::new();
+                if pkg.bundle_type != BundleType::Standard {
+                    notes.push(pkg.bundle_type.to_string());
+                }
+                if pkg.jvm_impl != "hotspot" {
+                    notes.push(pkg.jvm_impl.clone());
+                }
+                let lts = if pkg.is_lts { "LTS".green() } else { "-".bright_black() };
+                println!("  {:<7} {:<14} {:<18} {:>8}  {:<5} {}",
+                         major_text.bold().cyan(),
+                         pkg.vendor,
+                         pkg.version.white(),
+                         format!("{} MB", pkg.size / 1024 / 1024).bright_black(),
+                         lts,
+                         notes.join(", ").cyan()
+                );
+                // print the major version once per group
+                major_text.clear();
+            }
         }
     }
 
     println!("\n{}", "-".repeat(80).bright_black());
-    println!("Total: {} version(s)", versions.len());
+    println!("Total: {} package(s) in {} major version(s)", total, grouped.len());
     println!("\nUse: {} to download and install",
-             "jsh download <version>".green());
+             "jsh download <version> --vendor <vendor>".green());
 
     Ok(())
 }
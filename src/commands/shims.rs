@@ -0,0 +1,55 @@
+use crate::error::{JdkError, Result};
+use crate::jdk::JdkManager;
+use crate::jdk::resolver;
+use crate::jdk::shims;
+use colored::*;
+use std::process::Command;
+
+pub fn rehash_command() -> Result<()> {
+    let manager = JdkManager::new()?;
+    let tools = shims::rehash(&manager)?;
+    let dir = shims::shims_dir()?;
+
+    println!("{}", format!("[OK] Created {} shim(s) in {}", tools.len(), dir.display()).green());
+
+    if !shims::is_on_path() {
+        println!("\n{}", "Note:".yellow().bold());
+        println!("  Add the shims directory to the front of your PATH:");
+        #[cfg(target_os = "windows")]
+        println!("    {}", format!("setx PATH \"{};%PATH%\"", dir.display()).green());
+        #[cfg(not(target_os = "windows"))]
+        println!("    {}", format!("export PATH=\"{}:$PATH\"", dir.display()).green());
+    }
+
+    Ok(())
+}
+
+/// Run a JDK tool from the JDK selected for the current directory or shell
+pub fn exec_command(tool: &str, args: &[String]) -> Result<()> {
+    let manager = JdkManager::new()?;
+    let cwd = std::env::current_dir()?;
+    let resolution = resolver::resolve(&manager, &cwd)?.ok_or(JdkError::NoActiveJdk)?;
+
+    let program = shims::tool_path(&resolution.info.path, tool);
+    if !program.is_file() {
+        return Err(JdkError::InvalidPath(format!(
+            "'{}' is not available in JDK {} ({})",
+            tool, resolution.key, resolution.info.path.display()
+        )));
+    }
+
+    let mut command = Command::new(&program);
+    command.args(args).env("JAVA_HOME", &resolution.info.path);
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        use std::os::unix::process::CommandExt;
+        Err(JdkError::IoError(command.exec()))
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let status = command.status()?;
+        std::process::exit(status.code().unwrap_or(1));
+    }
+}
@@ -1,6 +1,12 @@
 use crate::env::{EnvUpdater, get_env_updater};
+#[cfg(not(target_os = "windows"))]
+use crate::env::shell::{SHELL_INTEGRATION_VAR, Shell};
+#[cfg(not(target_os = "windows"))]
+use crate::env::unix::UnixEnvUpdater;
 use crate::error::Result;
 use crate::jdk::JdkManager;
+use crate::jdk::resolver;
+use crate::jdk::shims;
 use colored::*;
 
 pub fn use_command(version: &str) -> Result<()> {
@@ -8,11 +14,22 @@ pub fn use_command(version: &str) -> Result<()> {
 
     println!("{}", format!("Switching to JDK {}...", version).cyan());
 
-    let jdk = manager.switch_jdk(version)?;
+    let (key, jdk) = manager.switch_jdk(version)?;
 
     println!("\n{}", "Updated configuration:".bold());
-    println!("  {} JDK {}", "Version:".bright_black(), version.green());
+    println!("  {} JDK {}", "Version:".bright_black(), key.green());
     println!("  {} {}", "Path:".bright_black(), jdk.path.display());
+    if jdk.is_jre() {
+        println!("  {}", "This is a JRE: java runs, but javac and other JDK tools are not available.".yellow());
+    }
+
+    // Shims resolve the JDK at run time, PATH stays untouched
+    if shims::is_on_path() {
+        println!("\n{} {}", "[OK]".green().bold(), "Successfully switched to JDK".green());
+        println!("  {}", "Shims are on PATH, no environment changes needed.".bright_black());
+        print_project_note();
+        return Ok(());
+    }
 
     // Update environment variables
     println!("\n{}", "Updating environment variables...".cyan());
@@ -21,6 +38,8 @@ pub fn use_command(version: &str) -> Result<()> {
 
     println!("\n{} {}", "[OK]".green().bold(), "Successfully switched to JDK".green());
 
+    print_project_note();
+
     #[cfg(target_os = "windows")]
     {
         println!("\n{}", "Note:".yellow().bold());
@@ -32,13 +51,30 @@ pub fn use_command(version: &str) -> Result<()> {
     #[cfg(not(target_os = "windows"))]
     {
         println!("\n{}", "Note:".yellow().bold());
-        let rc_path = if std::env::var("SHELL").unwrap_or_default().contains("zsh") {
-            "~/.zshrc"
+        if std::env::var(SHELL_INTEGRATION_VAR).is_ok() {
+            println!("  The current shell will be updated by the jsh shell function.");
         } else {
-            "~/.bashrc"
-        };
-        println!("  Please run: {}", format!("source {}", rc_path).green());
+            let shell = Shell::detect();
+            if let Ok(env_file) = UnixEnvUpdater::env_file_path(shell) {
+                let reload = UnixEnvUpdater::reload_hint(shell, &env_file);
+                println!("  Please run: {}", reload.green());
+            }
+            println!("  To apply switches to the current shell automatically, add:");
+            println!("    {}", shell.init_hint().green());
+        }
+        println!("  To undo the changes to your shell files, run: {}", "jsh env --restore".green());
     }
     
     Ok(())
 }
+
+/// Tell the user when a project file overrides the global JDK here
+fn print_project_note() {
+    if let Ok(cwd) = std::env::current_dir()
+        && let Some(file) = resolver::find_version_file(&cwd)
+    {
+        println!("\n{}", "Note:".yellow().bold());
+        println!("  {} pins JDK {} for this directory.", file.path.display(), file.version);
+        println!("  It takes precedence over the global JDK here.");
+    }
+}
@@ -2,7 +2,22 @@ use crate::error::{JdkError, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that puts config and data into a single directory
+pub const HOME_VAR: &str = "JSH_HOME";
+
+/// File next to the executable that keeps everything in the executable directory
+pub const PORTABLE_MARKER: &str = "jsh.portable";
+
+/// $var, or `fallback` relative to the home directory when unset
+#[cfg(not(target_os = "windows"))]
+fn xdg_dir(var: &str, fallback: &str) -> Option<PathBuf> {
+    std::env::var_os(var)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(fallback)))
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JdkInfo {
@@ -10,17 +25,123 @@ pub struct JdkInfo {
     pub version: String,
     pub vendor: Option<String>,
     pub java_version: Option<String>,
+    #[serde(default)]
+    pub arch: Option<String>,
+    /// GraalVM builds that ship the native-image tool
+    #[serde(default)]
+    pub native_image: bool,
+    /// JVM implementation: hotspot, openj9 or graal
+    #[serde(default)]
+    pub jvm_impl: Option<String>,
+    /// "jdk", or "jre" for runtimes without javac
+    #[serde(default)]
+    pub image_type: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl JdkInfo {
+    /// short vendor id used in registry keys (eg: "Eclipse Temurin" -> "temurin")
+    pub fn vendor_id(&self) -> String {
+        self.vendor
+            .as_deref()
+            .and_then(|v| v.split_whitespace().last())
+            .map(|v| v.to_lowercase())
+            .unwrap_or_else(|| "jdk".to_string())
+    }
+
+    /// registry key built from vendor and full version (eg: "temurin-17.0.9", "temurin-17.0.9-jre")
+    pub fn key(&self) -> String {
+        let version = self.java_version.as_deref().unwrap_or(&self.version);
+        if self.is_jre() {
+            return format!("{}-{}-jre", self.vendor_id(), version);
+        }
+        format!("{}-{}", self.vendor_id(), version)
+    }
+
+    /// runtime only image, no javac or other development tools
+    pub fn is_jre(&self) -> bool {
+        self.image_type.as_deref() == Some("jre")
+    }
+
+    /// numeric parts of the full version for ordering, "1.8.0_291" -> [8, 0, 291]
+    pub fn version_parts(&self) -> Vec<u32> {
+        let version = self.java_version.as_deref().unwrap_or(&self.version);
+        let mut parts: Vec<u32> = version
+            .split(|c: char| !c.is_ascii_digit())
+            .filter_map(|p| p.parse().ok())
+            .collect();
+        if parts.len() > 1 && parts[0] == 1 {
+            parts.remove(0);
+        }
+        parts
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Config {
     pub current_jdk: Option<String>,
     pub jdks: HashMap<String, JdkInfo>,
-    pub download_dir: PathBuf,
+    /// paths removed by the user that scanning should not register again
+    #[serde(default)]
+    pub ignored: Vec<PathBuf>,
+    /// url prefix (api base or download host) -> replacement urls tried in order when a connection fails,
+    /// eg: {"https://api.adoptium.net": ["https://mirror.example.com/adoptium", "https://api.adoptium.net"]}
+    #[serde(default)]
+    pub mirrors: HashMap<String, Vec<String>>,
 }
 
 impl Config {
+    /// directory holding config.json and the shell env files
     pub fn config_dir() -> Result<PathBuf> {
+        if let Some(home) = Self::home_override()? {
+            return Ok(home);
+        }
+        #[cfg(target_os = "windows")]
+        let base = dirs::config_dir();
+        #[cfg(not(target_os = "windows"))]
+        let base = xdg_dir("XDG_CONFIG_HOME", ".config");
+
+        base.map(|dir| dir.join("jsh"))
+            .ok_or_else(|| JdkError::ConfigError("Cannot find config directory".to_string()))
+    }
+
+    /// directory holding installed JDKs, shims and download caches
+    pub fn data_dir() -> Result<PathBuf> {
+        if let Some(home) = Self::home_override()? {
+            return Ok(home);
+        }
+        #[cfg(target_os = "windows")]
+        let base = dirs::data_local_dir();
+        #[cfg(not(target_os = "windows"))]
+        let base = xdg_dir("XDG_DATA_HOME", ".local/share");
+
+        base.map(|dir| dir.join("jsh"))
+            .ok_or_else(|| JdkError::ConfigError("Cannot find data directory".to_string()))
+    }
+
+    /// directory holding cached remote metadata, safe to delete
+    pub fn cache_dir() -> Result<PathBuf> {
+        if let Some(home) = Self::home_override()? {
+            return Ok(home.join("cache"));
+        }
+        // %LOCALAPPDATA%\jsh is already the data dir on Windows
+        #[cfg(target_os = "windows")]
+        let dir = dirs::cache_dir().map(|dir| dir.join("jsh").join("cache"));
+        #[cfg(not(target_os = "windows"))]
+        let dir = xdg_dir("XDG_CACHE_HOME", ".cache").map(|dir| dir.join("jsh"));
+
+        dir.ok_or_else(|| JdkError::ConfigError("Cannot find cache directory".to_string()))
+    }
+
+    /// JSH_HOME, or the executable directory in portable mode
+    fn home_override() -> Result<Option<PathBuf>> {
+        if let Some(home) = std::env::var_os(HOME_VAR).filter(|v| !v.is_empty()) {
+            return Ok(Some(PathBuf::from(home)));
+        }
+        let exe_dir = Self::exe_dir()?;
+        Ok(exe_dir.join(PORTABLE_MARKER).exists().then_some(exe_dir))
+    }
+
+    fn exe_dir() -> Result<PathBuf> {
         let exe_path = std::env::current_exe()
             .map_err(|e| JdkError::ConfigError(format!("Cannot get executable path: {}", e)))?;
         
@@ -30,12 +151,25 @@ impl Config {
         Ok(exe_dir.to_path_buf())
     }
 
+    /// directory jsh installs downloaded JDKs into
+    pub fn jdks_dir() -> Result<PathBuf> {
+        Ok(Self::data_dir()?.join("jdks"))
+    }
+
+    /// directory downloaded archives are cached in
+    pub fn downloads_dir() -> Result<PathBuf> {
+        Ok(Self::data_dir()?.join("downloads"))
+    }
+
     pub fn config_path() -> Result<PathBuf> {
         Ok(Self::config_dir()?.join("config.json"))
     }
 
     pub fn load() -> Result<Self> {
         let path = Self::config_path()?;
+        if !path.exists() {
+            Self::migrate_exe_dir_install()?;
+        }
 
         if !path.exists() {
             return Ok(Self::default());
@@ -44,8 +178,10 @@ impl Config {
         let content = fs::read_to_string(&path)
             .map_err(|e| JdkError::ConfigError(format!("Failed to read config: {}", e)))?;
 
-        serde_json::from_str(&content)
-            .map_err(|e| JdkError::ConfigError(format!("Failed to parse config: {}", e)))
+        let mut config: Self = serde_json::from_str(&content)
+            .map_err(|e| JdkError::ConfigError(format!("Failed to parse config: {}", e)))?;
+        config.migrate_legacy_keys();
+        Ok(config)
     }
 
     pub fn save(&self) -> Result<()> {
@@ -73,6 +209,106 @@ impl Config {
         self.jdks.get(key)
     }
 
+    pub fn remove_jdk(&mut self, key: &str) -> Option<JdkInfo> {
+        if self.current_jdk.as_deref() == Some(key) {
+            self.current_jdk = None;
+        }
+        self.jdks.remove(key)
+    }
+
+    pub fn find_key_by_path(&self, path: &Path) -> Option<String> {
+        self.jdks
+            .iter()
+            .find(|(_, info)| info.path == path)
+            .map(|(key, _)| key.clone())
+    }
+
+    /// pick a free registry key for a JDK, adding the arch and then a counter on collision
+    pub fn unique_key(&self, info: &JdkInfo) -> String {
+        let base = info.key();
+        if !self.jdks.contains_key(&base) {
+            return base;
+        }
+        if let Some(arch) = &info.arch {
+            let with_arch = format!("{}-{}", base, arch);
+            if !self.jdks.contains_key(&with_arch) {
+                return with_arch;
+            }
+        }
+        (2..)
+            .map(|n| format!("{}-{}", base, n))
+            .find(|k| !self.jdks.contains_key(k))
+            .unwrap()
+    }
+
+    /// older configs keyed JDKs by major version only, rename them to composite keys
+    fn migrate_legacy_keys(&mut self) {
+        let legacy: Vec<String> = self
+            .jdks
+            .iter()
+            .filter(|(key, info)| **key == info.version)
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        for old_key in legacy {
+            let info = self.jdks.remove(&old_key).unwrap();
+            let new_key = self.unique_key(&info);
+            if self.current_jdk.as_deref() == Some(old_key.as_str()) {
+                self.current_jdk = Some(new_key.clone());
+            }
+            self.jdks.insert(new_key, info);
+        }
+    }
+
+    /// earlier versions kept everything next to the executable, move it to the XDG dirs
+    fn migrate_exe_dir_install() -> Result<()> {
+        let old_dir = Self::exe_dir()?;
+        let old_config = old_dir.join("config.json");
+        if Self::home_override()?.is_some() || !old_config.is_file() {
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(&old_config)
+            .map_err(|e| JdkError::ConfigError(format!("Failed to read config: {}", e)))?;
+        let mut config: Self = serde_json::from_str(&content)
+            .map_err(|e| JdkError::ConfigError(format!("Failed to parse config: {}", e)))?;
+
+        let data_dir = Self::data_dir()?;
+        fs::create_dir_all(&data_dir)
+            .map_err(|e| JdkError::ConfigError(format!("Failed to create data dir: {}", e)))?;
+        for name in ["jdks", "shims"] {
+            let from = old_dir.join(name);
+            let to = data_dir.join(name);
+            // rename fails across filesystems, JDKs then stay where they are
+            if from.is_dir() && !to.exists() && fs::rename(&from, &to).is_ok() {
+                config.relocate(&from, &to);
+            }
+        }
+        config.save()?;
+        fs::remove_file(&old_config).ok();
+
+        eprintln!("Moved jsh config from {} to {}", old_dir.display(), Self::config_dir()?.display());
+        if data_dir.join("shims").is_dir() {
+            eprintln!("Shims now live in {}, update your PATH", data_dir.join("shims").display());
+        }
+        Ok(())
+    }
+
+    /// rewrite registered paths under `from` to live under `to`
+    fn relocate(&mut self, from: &Path, to: &Path) {
+        let moved = |path: &Path| path.strip_prefix(from).ok().map(|rest| to.join(rest));
+        for info in self.jdks.values_mut() {
+            if let Some(path) = moved(&info.path) {
+                info.path = path;
+            }
+        }
+        for path in self.ignored.iter_mut() {
+            if let Some(new_path) = moved(path) {
+                *path = new_path;
+            }
+        }
+    }
+
     pub fn set_current(&mut self, key: String) {
         self.current_jdk = Some(key);
     }
@@ -82,16 +318,90 @@ impl Config {
     }
 }
 
-impl Default for Config {
-    fn default() -> Self {
-        let download_dir = Self::config_dir()
-            .unwrap_or_else(|_| PathBuf::from("."))
-            .join("downloads");
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        Self {
-            current_jdk: None,
-            jdks: HashMap::new(),
-            download_dir,
+    fn jdk(vendor: Option<&str>, java_version: &str, arch: &str) -> JdkInfo {
+        JdkInfo {
+            path: PathBuf::from(format!("/jdks/{:?}-{}-{}", vendor, java_version, arch)),
+            version: java_version.split('.').next().unwrap().to_string(),
+            vendor: vendor.map(|v| v.to_string()),
+            java_version: Some(java_version.to_string()),
+            arch: Some(arch.to_string()),
+            native_image: false,
+            jvm_impl: None,
+            image_type: None,
         }
     }
+
+    #[test]
+    fn test_unique_key() {
+        let mut config = Config::default();
+        let temurin = jdk(Some("Eclipse Temurin"), "17.0.9", "x64");
+        assert_eq!(config.unique_key(&temurin), "temurin-17.0.9");
+        config.add_jdk(config.unique_key(&temurin), temurin);
+
+        let zulu = jdk(Some("Azul Zulu"), "17.0.2", "x64");
+        assert_eq!(config.unique_key(&zulu), "zulu-17.0.2");
+
+        let temurin_arm = jdk(Some("Eclipse Temurin"), "17.0.9", "aarch64");
+        assert_eq!(config.unique_key(&temurin_arm), "temurin-17.0.9-aarch64");
+        config.add_jdk(config.unique_key(&temurin_arm), temurin_arm.clone());
+        assert_eq!(config.unique_key(&temurin_arm), "temurin-17.0.9-2");
+
+        assert_eq!(config.unique_key(&jdk(None, "11.0.8", "x64")), "jdk-11.0.8");
+
+        let mut temurin_jre = jdk(Some("Eclipse Temurin"), "17.0.9", "x64");
+        temurin_jre.image_type = Some("jre".to_string());
+        assert_eq!(config.unique_key(&temurin_jre), "temurin-17.0.9-jre");
+    }
+
+    #[test]
+    fn test_version_parts() {
+        assert_eq!(jdk(None, "1.8.0_291", "x64").version_parts(), vec![8, 0, 291]);
+        assert_eq!(jdk(None, "17.0.9", "x64").version_parts(), vec![17, 0, 9]);
+        assert_eq!(jdk(None, "21", "x64").version_parts(), vec![21]);
+    }
+
+    #[test]
+    fn test_relocate() {
+        let mut config = Config::default();
+        let mut info = jdk(Some("Eclipse Temurin"), "17.0.9", "x64");
+        info.path = PathBuf::from("/opt/bin/jdks/jdk-17.0.9+9");
+        config.add_jdk("temurin-17.0.9".to_string(), info);
+        config.add_jdk("zulu-11.0.8".to_string(), jdk(Some("Azul Zulu"), "11.0.8", "x64"));
+        config.ignored.push(PathBuf::from("/opt/bin/jdks/jdk-21"));
+
+        config.relocate(Path::new("/opt/bin/jdks"), Path::new("/home/me/.local/share/jsh/jdks"));
+
+        assert_eq!(
+            config.get_jdk("temurin-17.0.9").unwrap().path,
+            PathBuf::from("/home/me/.local/share/jsh/jdks/jdk-17.0.9+9")
+        );
+        assert!(config.get_jdk("zulu-11.0.8").unwrap().path.starts_with("/jdks"));
+        assert_eq!(config.ignored[0], PathBuf::from("/home/me/.local/share/jsh/jdks/jdk-21"));
+    }
+
+    #[test]
+    fn test_migrate_legacy_keys() {
+        let mut config = Config::default();
+        config.add_jdk("17".to_string(), jdk(Some("OpenJDK"), "17.0.10", "x64"));
+        config.add_jdk("zulu-11.0.8".to_string(), jdk(Some("Azul Zulu"), "11.0.8", "x64"));
+        config.set_current("17".to_string());
+
+        config.migrate_legacy_keys();
+
+        assert!(config.get_jdk("openjdk-17.0.10").is_some());
+        assert!(config.get_jdk("zulu-11.0.8").is_some());
+        assert!(config.get_jdk("17").is_none());
+        assert_eq!(config.current_jdk.as_deref(), Some("openjdk-17.0.10"));
+    }
+
+    #[test]
+    fn test_old_config_with_download_dir() {
+        let json = r#"{"current_jdk": null, "jdks": {}, "download_dir": "C:\\jsh\\downloads"}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert!(!serde_json::to_string(&config).unwrap().contains("download_dir"));
+    }
 }
@@ -1,10 +1,22 @@
 use async_trait::async_trait;
-use crate::downloader::traits::{JdkPackage, JdkSource, detect_arch, detect_os, get_file_type};
+use crate::downloader::cache;
+use crate::downloader::traits::{
+    BundleType, ChecksumType, ImageType, JdkPackage, JdkSource, MAX_CONCURRENT_REQUESTS, detect_arch, detect_os, get_file_type, is_lts_version,
+};
 use crate::error::JdkError;
 use crate::error::Result;
+use futures_util::{StreamExt, stream};
 use reqwest::Client;
 use serde::Deserialize;
 
+const API_URL: &str = "https://api.adoptium.net/v3";
+
+/// largest page the feature_releases endpoint serves
+const PAGE_SIZE: usize = 20;
+
+/// Temurin only ships HotSpot, OpenJ9 builds moved to IBM Semeru
+const JVM_IMPL: &str = "hotspot";
+
 pub struct AdoptiumSource {
     client: Client,
 }
@@ -19,27 +31,20 @@ impl AdoptiumSource {
         }
     }
 
-    /// version + os + arch + lts to find jdk info
+    /// version + os + arch + lts + image to find jdk info
     async fn fetch_version_package(
         &self,
         version: u32,
         os: &str,
         arch: &str,
-        lts_versions: &[u32],
+        is_lts: bool,
+        image: ImageType,
     ) -> Result<JdkPackage> {
         let url = format!(
-            "https://api.adoptium.net/v3/assets/latest/{}/hotspot?os={}&architecture={}&image_type=jdk",
-            version, os, arch
+            "{}/assets/latest/{}/{}?os={}&architecture={}&image_type={}",
+            API_URL, version, JVM_IMPL, os, arch, image
         );
-        let mut response: Vec<AssetResponse> = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| JdkError::NetworkError(e.to_string()))?
-            .json()
-            .await
-            .map_err(|e| JdkError::NetworkError(e.to_string()))?;
+        let mut response: Vec<AssetResponse> = cache::get_json(self.client.get(&url)).await?;
 
         let asset = response
             .pop()
@@ -53,13 +58,65 @@ impl AdoptiumSource {
             arch: asset.binary.architecture.clone(),
             download_url: asset.binary.package.link.clone(),
             size: asset.binary.package.size,
-            file_type: get_file_type(&os).to_string(),
-            is_lts: lts_versions.contains(&version),
+            file_type: archive_type(&asset.binary.package.link, os).to_string(),
+            is_lts,
             checksum: Some(asset.binary.package.checksum),
+            checksum_type: ChecksumType::Sha256,
+            bundle_type: BundleType::Standard,
+            image_type: image,
+            jvm_impl: JVM_IMPL.to_string(),
+        })
+    }
+
+    /// one page of GA releases of a feature version, newest first
+    async fn fetch_release_page(&self, major_version: u32, image: ImageType, page: usize) -> Result<Vec<Release>> {
+        let url = format!("{}/assets/feature_releases/{}/ga", API_URL, major_version);
+        let os = detect_os();
+        let request = self.client.get(&url).query(&[
+            ("os", os.as_str()),
+            ("architecture", detect_arch().as_str()),
+            ("image_type", &image.to_string()),
+            ("jvm_impl", JVM_IMPL),
+            ("sort_order", "DESC"),
+            ("page_size", &PAGE_SIZE.to_string()),
+            ("page", &page.to_string()),
+        ]);
+        cache::get_json(request).await
+    }
+}
+
+impl Release {
+    fn into_package(self, os: &str, image: ImageType) -> Option<JdkPackage> {
+        let binary = self.binaries.into_iter().next()?;
+        let file_type = archive_type(&binary.package.link, os);
+        Some(JdkPackage {
+            version: self.version_data.semver,
+            major_version: self.version_data.major,
+            vendor: "temurin".to_string(),
+            os: binary.os,
+            arch: binary.architecture,
+            download_url: binary.package.link,
+            size: binary.package.size,
+            file_type: file_type.to_string(),
+            is_lts: is_lts_version(self.version_data.major),
+            checksum: Some(binary.package.checksum),
+            checksum_type: ChecksumType::Sha256,
+            bundle_type: BundleType::Standard,
+            image_type: image,
+            jvm_impl: JVM_IMPL.to_string(),
         })
     }
 }
 
+/// source tarballs are tar.gz on every platform
+fn archive_type(link: &str, os: &str) -> &'static str {
+    match link {
+        l if l.ends_with(".tar.gz") => "tar.gz",
+        l if l.ends_with(".zip") => "zip",
+        _ => get_file_type(os),
+    }
+}
+
 // ============adoptium api structs============
 #[derive(Deserialize, Debug)]
 pub struct AvailableReleases {
@@ -73,6 +130,11 @@ struct AssetResponse {
     // release_name: String,
 }
 #[derive(Deserialize, Debug)]
+struct Release {
+    binaries: Vec<Binary>,
+    version_data: Version,
+}
+#[derive(Deserialize, Debug)]
 struct Version {
     major: u32,
     // minor: u32,
@@ -102,29 +164,82 @@ impl JdkSource for AdoptiumSource {
     }
 
     async fn fetch_version(&self) -> Result<Vec<JdkPackage>> {
-        let release_url = "https://api.adoptium.net/v3/info/available_releases";
-        let releases: AvailableReleases = self
-            .client
-            .get(release_url)
-            .send()
-            .await
-            .map_err(|e| JdkError::NetworkError(e.to_string()))?
-            .json()
-            .await
-            .map_err(|e| JdkError::NetworkError(e.to_string()))?;
+        let release_url = format!("{}/info/available_releases", API_URL);
+        let releases: AvailableReleases = cache::get_json(self.client.get(&release_url)).await?;
 
         let os = detect_os();
         let arch = detect_arch();
 
+        let lts = &releases.available_lts_releases;
+        let packages = stream::iter(releases.available_releases.iter().copied())
+            .map(|version| self.fetch_version_package(version, &os, &arch, lts.contains(&version), ImageType::Jdk))
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .filter_map(|pkg| async move { pkg.ok() })
+            .collect()
+            .await;
+        Ok(packages)
+    }
+
+    /// every GA release of a feature version, following the API's pagination
+    async fn list_releases(&self, major_version: u32, image: ImageType) -> Result<Vec<JdkPackage>> {
+        let os = detect_os();
         let mut packages = Vec::new();
-        for version in releases.available_releases {
-            if let Ok(pkg) = self
-                .fetch_version_package(version, &os, &arch, &releases.available_lts_releases)
-                .await
-            {
-                packages.push(pkg);
+        for page in 0.. {
+            let releases = match self.fetch_release_page(major_version, image, page).await {
+                Ok(releases) => releases,
+                // pages past the end are answered with 404, anything else would leave the list incomplete
+                Err(JdkError::HttpStatus { status: 404, .. }) if page > 0 => break,
+                Err(e) => return Err(e),
+            };
+            let count = releases.len();
+            if count == 0 {
+                break;
+            }
+            packages.extend(releases.into_iter().filter_map(|r| r.into_package(&os, image)));
+            if count < PAGE_SIZE {
+                break;
             }
         }
         Ok(packages)
     }
+
+    /// only the requested feature version, not the whole catalogue
+    async fn find_package(&self, major_version: u32) -> Result<JdkPackage> {
+        self.find_image(major_version, ImageType::Jdk).await
+    }
+
+    async fn find_image(&self, major_version: u32, image: ImageType) -> Result<JdkPackage> {
+        let (os, arch) = (detect_os(), detect_arch());
+        self.fetch_version_package(major_version, &os, &arch, is_lts_version(major_version), image)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_release_into_package() {
+        let json = r#"[{
+            "binaries": [{
+                "architecture": "x64", "os": "linux", "image_type": "jdk", "jvm_impl": "hotspot",
+                "package": {
+                    "checksum": "e5e2cd4c1b1e8cf4b6c2d8ad4a4d4bc0b11b4b1b0f64b2e64bd4e4c9d4b1b2c3",
+                    "link": "https://github.com/adoptium/temurin17-binaries/releases/download/jdk-17.0.5%2B8/OpenJDK17U-jdk_x64_linux_hotspot_17.0.5_8.tar.gz",
+                    "name": "OpenJDK17U-jdk_x64_linux_hotspot_17.0.5_8.tar.gz", "size": 191872975
+                }
+            }],
+            "release_name": "jdk-17.0.5+8",
+            "version_data": {"major": 17, "minor": 0, "security": 5, "build": 8, "semver": "17.0.5+8", "openjdk_version": "17.0.5+8"}
+        }, {"binaries": [], "release_name": "jdk-17.0.4+8", "version_data": {"major": 17, "semver": "17.0.4+8"}}]"#;
+        let releases: Vec<Release> = serde_json::from_str(json).unwrap();
+        let packages: Vec<JdkPackage> = releases.into_iter().filter_map(|r| r.into_package("linux", ImageType::Jdk)).collect();
+
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].version, "17.0.5+8");
+        assert!(packages[0].is_lts);
+        assert!(packages[0].download_url.ends_with("OpenJDK17U-jdk_x64_linux_hotspot_17.0.5_8.tar.gz"));
+        assert_eq!(packages[0].file_type, "tar.gz");
+    }
 }
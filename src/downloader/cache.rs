@@ -0,0 +1,226 @@
+use crate::config::Config;
+use crate::downloader::mirror;
+use crate::error::{JdkError, Result};
+use colored::Colorize;
+use reqwest::header::{ETAG, HeaderValue, IF_NONE_MATCH};
+use reqwest::{Client, Request, RequestBuilder, StatusCode};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// cached responses younger than this are used without asking the server
+const TTL_SECS: u64 = 60 * 60;
+
+/// set by `--refresh`, skips cached responses
+static REFRESH: AtomicBool = AtomicBool::new(false);
+
+/// only warn once per run about falling back to cached data
+static OFFLINE_WARNED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Serialize, Deserialize, Debug)]
+struct CacheEntry {
+    url: String,
+    etag: Option<String>,
+    /// unix time of the last successful fetch or revalidation
+    fetched_at: u64,
+    body: String,
+}
+
+/// Ignore cached metadata for the rest of this run
+pub fn set_refresh(refresh: bool) {
+    REFRESH.store(refresh, Ordering::Relaxed);
+}
+
+/// GET a JSON document through the metadata cache
+pub async fn get_json<T: DeserializeOwned>(request: RequestBuilder) -> Result<T> {
+    let body = get_text(request).await?;
+    serde_json::from_str(&body).map_err(|e| JdkError::NetworkError(e.to_string()))
+}
+
+/// GET a response body through the metadata cache: fresh entries are used as they are,
+/// stale ones are revalidated with their ETag and used as is when the network is down
+async fn get_text(request: RequestBuilder) -> Result<String> {
+    let (client, request) = request.build_split();
+    let request = request.map_err(|e| JdkError::NetworkError(e.to_string()))?;
+    let path = entry_path(request.url().as_str())?;
+    fetch(client, request, &path).await
+}
+
+async fn fetch(client: Client, mut request: Request, path: &Path) -> Result<String> {
+    let url = request.url().to_string();
+    let refresh = REFRESH.load(Ordering::Relaxed);
+    let now = now_secs();
+
+    let cached = if refresh { None } else { load(path) };
+    if let Some(entry) = &cached {
+        if now.saturating_sub(entry.fetched_at) < TTL_SECS {
+            return Ok(entry.body.clone());
+        }
+        if let Some(etag) = entry.etag.as_deref().and_then(|e| HeaderValue::from_str(e).ok()) {
+            request.headers_mut().insert(IF_NONE_MATCH, etag);
+        }
+    }
+
+    let response = match mirror::send(RequestBuilder::from_parts(client, request)).await {
+        Ok(response) => response,
+        Err(e) => return stale_or(e, cached, path),
+    };
+
+    let status = response.status();
+    if status == StatusCode::NOT_MODIFIED
+        && let Some(mut entry) = cached
+    {
+        entry.fetched_at = now;
+        save(path, &entry);
+        return Ok(entry.body);
+    }
+    if !status.is_success() {
+        return Err(JdkError::HttpStatus { url, status: status.as_u16() });
+    }
+
+    let etag = response
+        .headers()
+        .get(ETAG)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+    let body = response
+        .text()
+        .await
+        .map_err(|e| JdkError::NetworkError(e.to_string()))?;
+    // captive portals and broken mirrors answer 200 with an html page, never cache that
+    if let Err(e) = serde_json::from_str::<IgnoredAny>(&body) {
+        let e = JdkError::NetworkError(format!("{} returned invalid JSON: {}", url, e));
+        return stale_or(e, cached, path);
+    }
+    save(path, &CacheEntry { url, etag, fetched_at: now, body: body.clone() });
+    Ok(body)
+}
+
+/// a stale entry still beats no data at all when offline, `e` is returned without one
+fn stale_or(e: JdkError, cached: Option<CacheEntry>, path: &Path) -> Result<String> {
+    let Some(entry) = cached.or_else(|| load(path)) else {
+        return Err(e);
+    };
+    if !OFFLINE_WARNED.swap(true, Ordering::Relaxed) {
+        eprintln!("{}", format!("Network unavailable ({}), using cached metadata", e).yellow());
+    }
+    Ok(entry.body)
+}
+
+/// cache file for a url, named after its hash
+fn entry_path(url: &str) -> Result<PathBuf> {
+    let hash: String = Sha256::digest(url.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    Ok(Config::cache_dir()?.join("metadata").join(format!("{}.json", &hash[..32])))
+}
+
+fn load(path: &Path) -> Option<CacheEntry> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// best effort, a failed write only costs a refetch next time
+fn save(path: &Path, entry: &CacheEntry) {
+    let Some(dir) = path.parent() else {
+        return;
+    };
+    if std::fs::create_dir_all(dir).is_err() {
+        return;
+    }
+    // write to a temp file first so concurrent runs never read half an entry
+    let tmp = path.with_extension(format!("tmp{}", std::process::id()));
+    if let Ok(content) = serde_json::to_string(entry)
+        && std::fs::write(&tmp, content).is_ok()
+        && std::fs::rename(&tmp, path).is_err()
+    {
+        std::fs::remove_file(&tmp).ok();
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_fetch_revalidates_with_etag() {
+        let server = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v3/info/available_releases", server.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for reply in [
+                "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: 13\r\nConnection: close\r\n\r\n{\"lts\": [17]}",
+                "HTTP/1.1 304 Not Modified\r\nConnection: close\r\n\r\n",
+            ] {
+                let (mut stream, _) = server.accept().unwrap();
+                let mut buf = [0u8; 2048];
+                let n = stream.read(&mut buf).unwrap();
+                requests.push(String::from_utf8_lossy(&buf[..n]).to_lowercase());
+                stream.write_all(reply.as_bytes()).unwrap();
+            }
+            requests
+        });
+
+        let path = std::env::temp_dir().join(format!("jsh-cache-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let client = reqwest::Client::new();
+
+        assert_eq!(fetch(client.clone(), client.get(&url).build().unwrap(), &path).await.unwrap(), r#"{"lts": [17]}"#);
+        // fresh entries are served without a request
+        assert_eq!(fetch(client.clone(), client.get(&url).build().unwrap(), &path).await.unwrap(), r#"{"lts": [17]}"#);
+
+        let mut entry = load(&path).unwrap();
+        assert_eq!(entry.etag.as_deref(), Some("\"v1\""));
+        entry.fetched_at = 0;
+        save(&path, &entry);
+        assert_eq!(fetch(client.clone(), client.get(&url).build().unwrap(), &path).await.unwrap(), r#"{"lts": [17]}"#);
+        assert!(load(&path).unwrap().fetched_at > 0);
+
+        let requests = handle.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].contains("if-none-match: \"v1\""));
+
+        // server gone, the cached body is used
+        let mut entry = load(&path).unwrap();
+        entry.fetched_at = 0;
+        save(&path, &entry);
+        assert_eq!(fetch(client.clone(), client.get(&url).build().unwrap(), &path).await.unwrap(), r#"{"lts": [17]}"#);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_fetch_does_not_cache_invalid_json() {
+        let server = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v3/info/available_releases", server.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = server.accept().unwrap();
+            let mut buf = [0u8; 2048];
+            let _ = stream.read(&mut buf);
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 13\r\nConnection: close\r\n\r\n<html></html>")
+                .unwrap();
+        });
+
+        let path = std::env::temp_dir().join(format!("jsh-cache-html-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let client = reqwest::Client::new();
+
+        let err = fetch(client.clone(), client.get(&url).build().unwrap(), &path).await.unwrap_err();
+        assert!(err.to_string().contains("invalid JSON"));
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,173 @@
+use async_trait::async_trait;
+use crate::downloader::cache;
+use crate::downloader::mirror;
+use crate::downloader::traits::{
+    BundleType, ChecksumType, ImageType, JdkPackage, JdkSource, MAX_CONCURRENT_REQUESTS, detect_arch, detect_os,
+    get_file_type, is_lts_version, os_name,
+};
+use crate::error::{JdkError, Result};
+use futures_util::{StreamExt, stream};
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+const BASE_URL: &str = "https://corretto.aws";
+
+/// Index of the latest Corretto builds with their checksums, maintained by the Corretto team
+const INDEX_URL: &str =
+    "https://raw.githubusercontent.com/corretto/corretto-downloads/main/latest_links/indexmap_with_checksum.json";
+
+pub struct CorrettoSource {
+    client: Client,
+}
+
+impl CorrettoSource {
+    pub fn new() -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// os -> arch -> image type -> major version -> file type -> build
+    async fn fetch_index(&self) -> Result<CorrettoIndex> {
+        cache::get_json(self.client.get(INDEX_URL)).await
+    }
+
+    /// size from a HEAD request, the index does not publish it
+    async fn fetch_size(&self, url: &str) -> u64 {
+        mirror::send(self.client.head(url))
+            .await
+            .ok()
+            .and_then(|r| r.content_length())
+            .unwrap_or_default()
+    }
+
+    async fn to_package(&self, major_version: u32, file_type: &str, build: &CorrettoBuild) -> Option<JdkPackage> {
+        let download_url = format!("{}{}", BASE_URL, build.resource);
+        Some(JdkPackage {
+            version: parse_resource_version(&build.resource)?,
+            major_version,
+            vendor: "corretto".to_string(),
+            os: detect_os(),
+            arch: detect_arch(),
+            size: self.fetch_size(&download_url).await,
+            download_url,
+            file_type: file_type.to_string(),
+            is_lts: is_lts_version(major_version),
+            checksum: build.checksum_sha256.clone(),
+            checksum_type: ChecksumType::Sha256,
+            bundle_type: BundleType::Standard,
+            image_type: ImageType::Jdk,
+            jvm_impl: "hotspot".to_string(),
+        })
+    }
+
+    /// latest build of every feature version for this platform, optionally limited to one major version
+    async fn fetch_packages(&self, major_version: Option<u32>) -> Result<Vec<JdkPackage>> {
+        let index = self.fetch_index().await?;
+        let os = detect_os();
+        let file_type = get_file_type(&os);
+
+        let Some(majors) = index
+            .get(os_name(&os))
+            .and_then(|arches| arches.get(&detect_arch()))
+            .and_then(|images| images.get("jdk"))
+        else {
+            return Ok(Vec::new());
+        };
+
+        let builds: Vec<(u32, CorrettoBuild)> = majors
+            .iter()
+            .filter_map(|(major, files)| Some((major.parse::<u32>().ok()?, files.get(file_type)?.clone())))
+            .filter(|(major, _)| major_version.is_none_or(|m| m == *major))
+            .collect();
+        let mut packages: Vec<JdkPackage> = stream::iter(builds)
+            .map(|(major, build)| async move { self.to_package(major, file_type, &build).await })
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .filter_map(|pkg| async move { pkg })
+            .collect()
+            .await;
+        packages.sort_by_key(|p| std::cmp::Reverse(p.major_version));
+        Ok(packages)
+    }
+}
+
+/// "/downloads/resources/17.0.9.8.1/amazon-corretto-17.0.9.8.1-linux-x64.tar.gz" -> "17.0.9+8",
+/// Corretto appends the build and its own revision to the OpenJDK version
+fn parse_resource_version(resource: &str) -> Option<String> {
+    let corretto_version = resource.split('/').rev().nth(1)?;
+    let parts: Vec<u32> = corretto_version
+        .split('.')
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+    match parts.as_slice() {
+        // 8.392.08.1 -> 8.0.392+8
+        [8, update, build, ..] => Some(format!("8.0.{}+{}", update, build)),
+        [major, minor, security, build, ..] => Some(format!("{}.{}.{}+{}", major, minor, security, build)),
+        _ => None,
+    }
+}
+
+// ============corretto index structs============
+type CorrettoIndex = HashMap<String, HashMap<String, HashMap<String, HashMap<String, HashMap<String, CorrettoBuild>>>>>;
+
+#[derive(Deserialize, Debug, Clone)]
+struct CorrettoBuild {
+    resource: String,
+    #[serde(default)]
+    checksum_sha256: Option<String>,
+}
+// ============corretto index structs============
+
+#[async_trait]
+impl JdkSource for CorrettoSource {
+    fn name(&self) -> &str {
+        "Amazon Corretto"
+    }
+
+    async fn fetch_version(&self) -> Result<Vec<JdkPackage>> {
+        self.fetch_packages(None).await
+    }
+
+    /// only the requested feature version, one HEAD request instead of one per version
+    async fn find_package(&self, major_version: u32) -> Result<JdkPackage> {
+        self.fetch_packages(Some(major_version))
+            .await?
+            .pop()
+            .ok_or_else(|| JdkError::PackageNotFound(major_version.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_resource_version() {
+        assert_eq!(
+            parse_resource_version("/downloads/resources/17.0.9.8.1/amazon-corretto-17.0.9.8.1-linux-x64.tar.gz").as_deref(),
+            Some("17.0.9+8")
+        );
+        assert_eq!(
+            parse_resource_version("/downloads/resources/8.392.08.1/amazon-corretto-8.392.08.1-windows-x64-jdk.zip").as_deref(),
+            Some("8.0.392+8")
+        );
+        assert_eq!(parse_resource_version("/downloads/latest/amazon-corretto-17-x64-linux-jdk.tar.gz"), None);
+    }
+
+    #[test]
+    fn test_index_lookup() {
+        let json = r#"{"linux": {"x64": {"jdk": {"21": {"tar.gz": {
+            "checksum": "5c1b4e0d2f9f2f1d5b6a7e8c9d0a1b2c",
+            "checksum_sha256": "d9b2f4e6a8c0e2f4a6b8d0f2e4a6c8e0b2d4f6a8c0e2f4a6b8d0f2e4a6c8e0b2",
+            "resource": "/downloads/resources/21.0.1.12.1/amazon-corretto-21.0.1.12.1-linux-x64.tar.gz"
+        }}}}}}"#;
+        let index: CorrettoIndex = serde_json::from_str(json).unwrap();
+        let build = &index["linux"]["x64"]["jdk"]["21"]["tar.gz"];
+        assert_eq!(parse_resource_version(&build.resource).as_deref(), Some("21.0.1+12"));
+        assert!(build.checksum_sha256.is_some());
+    }
+}
@@ -0,0 +1,218 @@
+use async_trait::async_trait;
+use crate::downloader::cache;
+use crate::downloader::traits::{BundleType, ChecksumType, ImageType, JdkPackage, JdkSource, detect_arch, detect_os, get_file_type, os_name};
+use crate::error::JdkError;
+use crate::error::Result;
+use reqwest::Client;
+use serde::Deserialize;
+
+const API_URL: &str = "https://api.foojay.io/disco/v3.0";
+
+/// foojay Disco API, one query returns packages of every distribution it knows
+pub struct DiscoSource {
+    client: Client,
+}
+
+impl DiscoSource {
+    pub fn new() -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// latest GA packages for this platform, optionally limited to one major version
+    async fn fetch_packages(&self, major_version: Option<u32>) -> Result<Vec<DiscoPackage>> {
+        let mut query = vec![("latest", "available".to_string())];
+        if let Some(major) = major_version {
+            query.push(("version", major.to_string()));
+        }
+        self.query_packages(query).await
+    }
+
+    /// GA packages for this platform matching `filters`
+    async fn query_packages(&self, filters: Vec<(&str, String)>) -> Result<Vec<DiscoPackage>> {
+        let os = detect_os();
+        let mut query = vec![
+            ("os", os_name(&os).to_string()),
+            ("architecture", detect_arch()),
+            ("archive_type", get_file_type(&os).to_string()),
+            ("package_type", "jdk".to_string()),
+            ("release_status", "ga".to_string()),
+            ("directly_downloadable", "true".to_string()),
+        ];
+        query.extend(filters);
+
+        let request = self.client.get(format!("{}/packages", API_URL)).query(&query);
+        let response: DiscoResponse<DiscoPackage> = cache::get_json(request).await?;
+
+        Ok(response.result.into_iter().filter(|p| p.is_plain()).collect())
+    }
+
+    /// direct download link and checksum, only served per package
+    async fn fetch_package_info(&self, id: &str) -> Result<DiscoPackageInfo> {
+        let url = format!("{}/ids/{}", API_URL, id);
+        let mut response: DiscoResponse<DiscoPackageInfo> = cache::get_json(self.client.get(&url)).await?;
+        response
+            .result
+            .pop()
+            .ok_or_else(|| JdkError::PackageNotFound(id.to_string()))
+    }
+}
+
+/// Disco distribution name to the vendor name jsh uses (eg: "sap_machine" -> "sapmachine")
+fn vendor_name(distribution: &str) -> String {
+    if distribution.starts_with("graalvm") {
+        return "graalvm".to_string();
+    }
+    distribution.replace('_', "")
+}
+
+// ============disco api structs============
+#[derive(Deserialize, Debug)]
+struct DiscoResponse<T> {
+    result: Vec<T>,
+}
+#[derive(Deserialize, Debug)]
+struct DiscoPackage {
+    id: String,
+    archive_type: String,
+    distribution: String,
+    major_version: u32,
+    java_version: String,
+    #[serde(default)]
+    term_of_support: Option<String>,
+    #[serde(default)]
+    lib_c_type: Option<String>,
+    #[serde(default)]
+    javafx_bundled: bool,
+    #[serde(default)]
+    size: u64,
+    #[serde(default)]
+    feature: Vec<serde_json::Value>,
+    links: DiscoLinks,
+}
+#[derive(Deserialize, Debug)]
+struct DiscoLinks {
+    pkg_download_redirect: String,
+}
+#[derive(Deserialize, Debug)]
+struct DiscoPackageInfo {
+    direct_download_uri: String,
+    #[serde(default)]
+    checksum: Option<String>,
+    #[serde(default)]
+    checksum_type: Option<String>,
+}
+// ============disco api structs============
+
+impl DiscoPackage {
+    /// skip musl and feature builds (CRaC, Leyden, ...) that share the query
+    fn is_plain(&self) -> bool {
+        self.lib_c_type.as_deref() != Some("musl") && self.feature.is_empty()
+    }
+
+    fn into_package(self) -> JdkPackage {
+        let jvm_impl = match self.distribution.as_str() {
+            "semeru" | "semeru_certified" => "openj9",
+            d if d.starts_with("graalvm") || d == "mandrel" => "graal",
+            _ => "hotspot",
+        };
+        JdkPackage {
+            // Disco versions may carry a trailing "-LTS"
+            version: self.java_version.trim_end_matches("-LTS").to_string(),
+            major_version: self.major_version,
+            vendor: vendor_name(&self.distribution),
+            os: detect_os(),
+            arch: detect_arch(),
+            download_url: self.links.pkg_download_redirect,
+            size: self.size,
+            file_type: self.archive_type,
+            is_lts: self.term_of_support.as_deref() == Some("lts"),
+            checksum: None,
+            checksum_type: ChecksumType::Sha256,
+            bundle_type: if self.javafx_bundled { BundleType::Full } else { BundleType::Standard },
+            image_type: ImageType::Jdk,
+            jvm_impl: jvm_impl.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl JdkSource for DiscoSource {
+    fn name(&self) -> &str {
+        "foojay Disco API"
+    }
+
+    async fn fetch_version(&self) -> Result<Vec<JdkPackage>> {
+        Ok(self
+            .fetch_packages(None)
+            .await?
+            .into_iter()
+            .map(DiscoPackage::into_package)
+            .collect())
+    }
+
+    /// every distribution's JDK releases of a major version, not only the latest
+    async fn list_releases(&self, major_version: u32, image: ImageType) -> Result<Vec<JdkPackage>> {
+        if image != ImageType::Jdk {
+            return Err(JdkError::PackageNotFound(format!("{} ({} image)", major_version, image)));
+        }
+        Ok(self
+            .query_packages(vec![("jdk_version", major_version.to_string())])
+            .await?
+            .into_iter()
+            .map(DiscoPackage::into_package)
+            .collect())
+    }
+
+    async fn find_package(&self, major_version: u32) -> Result<JdkPackage> {
+        let pkg = self
+            .fetch_packages(Some(major_version))
+            .await?
+            .into_iter()
+            .find(|p| p.major_version == major_version && !p.javafx_bundled)
+            .ok_or_else(|| JdkError::PackageNotFound(major_version.to_string()))?;
+        let info = self.fetch_package_info(&pkg.id).await?;
+
+        let mut package = pkg.into_package();
+        package.download_url = info.direct_download_uri;
+        if info.checksum_type.as_deref() == Some("sha256") {
+            package.checksum = info.checksum.filter(|c| !c.is_empty());
+        }
+        Ok(package)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_package() {
+        let json = r#"{"result": [{
+            "id": "4e3d3f5e0b6a8d1c2f9e7a6b5c4d3e2f", "archive_type": "tar.gz", "distribution": "sap_machine",
+            "major_version": 21, "java_version": "21.0.1", "distribution_version": "21.0.1",
+            "latest_build_available": true, "release_status": "ga", "term_of_support": "lts",
+            "operating_system": "linux", "lib_c_type": "glibc", "architecture": "x64", "package_type": "jdk",
+            "javafx_bundled": false, "directly_downloadable": true, "size": 203423744, "feature": [],
+            "links": {"pkg_info_uri": "https://api.foojay.io/disco/v3.0/ids/4e3d3f5e0b6a8d1c2f9e7a6b5c4d3e2f",
+                      "pkg_download_redirect": "https://api.foojay.io/disco/v3.0/ids/4e3d3f5e0b6a8d1c2f9e7a6b5c4d3e2f/redirect"}
+        }, {
+            "id": "9a8b7c6d", "archive_type": "tar.gz", "distribution": "zulu", "major_version": 21,
+            "java_version": "21.0.1+12", "lib_c_type": "musl", "links": {"pkg_download_redirect": ""}
+        }], "message": ""}"#;
+        let response: DiscoResponse<DiscoPackage> = serde_json::from_str(json).unwrap();
+        let mut packages: Vec<DiscoPackage> = response.result.into_iter().filter(|p| p.is_plain()).collect();
+
+        assert_eq!(packages.len(), 1);
+        let pkg = packages.pop().unwrap().into_package();
+        assert_eq!(pkg.vendor, "sapmachine");
+        assert_eq!(pkg.major_version, 21);
+        assert!(pkg.is_lts);
+        assert_eq!(pkg.bundle_type, BundleType::Standard);
+        assert_eq!(vendor_name("graalvm_community"), "graalvm");
+    }
+}
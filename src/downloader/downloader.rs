@@ -1,11 +1,19 @@
 use crate::config::Config;
+use crate::downloader::mirror;
+use crate::downloader::traits::ChecksumType;
 use crate::error::{JdkError, Result};
 use futures_util::StreamExt;
-use reqwest::{Client};
-use std::path::{PathBuf};
-use tokio::fs::File;
+use reqwest::header::RANGE;
+use reqwest::{Client, StatusCode};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use tokio::fs::OpenOptions;
 use tokio::io::AsyncWriteExt;
 
+/// tries per download, every retry resumes from the part file
+const MAX_ATTEMPTS: u32 = 3;
+
 pub struct Downloader {
     client: Client,
     download_dir: PathBuf,
@@ -13,8 +21,8 @@ pub struct Downloader {
 
 impl Downloader {
     pub fn new() -> Result<Self> {
-        let download_dir = Config::config_dir()?.join("downloads");
-        std::fs::create_dir_all(&download_dir).map_err(|e| JdkError::IoError(e))?;
+        let download_dir = Config::downloads_dir()?;
+        std::fs::create_dir_all(&download_dir).map_err(JdkError::IoError)?;
         Ok(Self {
             client: Client::builder()
                 .timeout(std::time::Duration::from_secs(600))
@@ -24,10 +32,15 @@ impl Downloader {
         })
     }
 
+    /// download url to download dir, verifying the checksum when one is given.
+    /// data goes to `<filename>.part` first and is renamed once complete and verified,
+    /// an interrupted download resumes from the part file with a Range request
     pub async fn download_file<F>(
         &self,
         url: &str,
         filename: &str,
+        checksum: Option<(&str, ChecksumType)>,
+        size: Option<u64>,
         on_progress: F,
     ) -> Result<PathBuf>
     where
@@ -37,36 +50,319 @@ impl Downloader {
         // file exists
         if target_path.exists() {
             println!("File already exists: {}", target_path.display());
-            println!("Using existing file...");
-            return Ok(target_path);
+            match verify_download(&target_path, checksum, size) {
+                Ok(()) => {
+                    println!("Verified, using existing file...");
+                    return Ok(target_path);
+                }
+                Err(e) => {
+                    println!("{}, downloading again...", e);
+                    std::fs::remove_file(&target_path).map_err(JdkError::IoError)?;
+                }
+            }
+        }
+
+        let part_path = self.download_dir.join(format!("{}.part", filename));
+        // hashed as it streams in, only the bytes already in the part file are read back
+        let mut hasher = match checksum {
+            Some((_, checksum_type)) => Some(Hasher::from_file(&part_path, checksum_type)?),
+            None => None,
+        };
+        let mut attempt = 1;
+        loop {
+            match self.fetch_part(url, &part_path, size, hasher.as_mut(), &on_progress).await {
+                Ok(()) => break,
+                Err(JdkError::NetworkError(e)) if attempt < MAX_ATTEMPTS => {
+                    println!("\nConnection lost ({}), resuming...", e);
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
         }
 
-        let response = self
-            .client
-            .get(url)
-            .send()
+        let verified = match (checksum, hasher) {
+            (Some((expected, _)), Some(hasher)) => compare_checksum(expected, &hasher.finish()),
+            _ => verify_download(&part_path, None, size),
+        };
+        // a complete but corrupt part file would be resumed forever, drop it
+        if let Err(e) = verified {
+            tokio::fs::remove_file(&part_path).await.ok();
+            return Err(e);
+        }
+        tokio::fs::rename(&part_path, &target_path)
             .await
-            .map_err(|e| JdkError::NetworkError(e.to_string()))?;
+            .map_err(JdkError::IoError)?;
+        Ok(target_path)
+    }
+
+    /// append the rest of url to the part file, starting after the bytes already on disk
+    async fn fetch_part<F>(
+        &self,
+        url: &str,
+        part_path: &Path,
+        size: Option<u64>,
+        mut hasher: Option<&mut Hasher>,
+        on_progress: &F,
+    ) -> Result<()>
+    where
+        F: Fn(u64, u64),
+    {
+        let mut downloaded = tokio::fs::metadata(part_path).await.map(|m| m.len()).unwrap_or(0);
+        if size.is_some_and(|size| downloaded >= size) {
+            return Ok(());
+        }
 
+        let mut request = self.client.get(url);
+        if downloaded > 0 {
+            request = request.header(RANGE, format!("bytes={}-", downloaded));
+        }
+        let response = mirror::send(request).await?;
+        let status = response.status();
+        // the part file already holds everything the server has
+        if status == StatusCode::RANGE_NOT_SATISFIABLE {
+            return Ok(());
+        }
+        if !status.is_success() {
+            return Err(JdkError::DownloadError(format!("{} returned HTTP {}", url, status)));
+        }
+
+        // servers without Range support send the whole file again
+        let resumed = status == StatusCode::PARTIAL_CONTENT;
+        if resumed {
+            println!("Resuming at {} MB", downloaded / 1024 / 1024);
+        } else {
+            downloaded = 0;
+            if let Some(hasher) = hasher.as_deref_mut() {
+                hasher.reset();
+            }
+        }
+        // 0 when neither the server nor the source knows the size
         let total_size = response
             .content_length()
-            .ok_or_else(|| JdkError::DownloadError("Unknown file size".to_string()))?;
-        let mut file = File::create(&target_path)
+            .map(|len| len + downloaded)
+            .or(size)
+            .unwrap_or(0);
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resumed)
+            .truncate(!resumed)
+            .open(part_path)
             .await
-            .map_err(|e| JdkError::IoError(e))?;
+            .map_err(JdkError::IoError)?;
 
-        let mut downloaded: u64 = 0;
         let mut stream = response.bytes_stream();
-
         while let Some(chunk_result) = stream.next().await {
-            let chunk = chunk_result.map_err(|e| JdkError::NetworkError(e.to_string()))?;
+            let chunk = match chunk_result {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    // keep what was received for the next attempt
+                    file.flush().await.map_err(JdkError::IoError)?;
+                    return Err(JdkError::NetworkError(e.to_string()));
+                }
+            };
             file.write_all(&chunk)
                 .await
-                .map_err(|e| JdkError::IoError(e))?;
+                .map_err(JdkError::IoError)?;
+            if let Some(hasher) = hasher.as_deref_mut() {
+                hasher.update(&chunk);
+            }
             downloaded += chunk.len() as u64;
             on_progress(downloaded, total_size);
         }
-        file.flush().await.map_err(|e| JdkError::IoError(e))?;
-        Ok(target_path)
+        file.flush().await.map_err(JdkError::IoError)?;
+
+        if total_size == 0 {
+            on_progress(downloaded, downloaded);
+        } else if downloaded < total_size {
+            return Err(JdkError::NetworkError(format!(
+                "connection closed after {} of {} bytes",
+                downloaded, total_size
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// check a downloaded file against the expected checksum, or the expected size when no checksum is published
+fn verify_download(path: &Path, checksum: Option<(&str, ChecksumType)>, size: Option<u64>) -> Result<()> {
+    if let Some((expected, checksum_type)) = checksum {
+        return verify_file_checksum(path, expected, checksum_type);
+    }
+    if let Some(expected) = size {
+        let actual = std::fs::metadata(path).map_err(JdkError::IoError)?.len();
+        if actual != expected {
+            return Err(JdkError::DownloadError(format!(
+                "Size mismatch: expected {} bytes, got {}",
+                expected, actual
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// hash an existing file and compare it with the expected checksum
+pub fn verify_file_checksum(path: &Path, expected: &str, checksum_type: ChecksumType) -> Result<()> {
+    let mut file = std::fs::File::open(path).map_err(JdkError::IoError)?;
+    let mut hasher = Hasher::new(checksum_type);
+    std::io::copy(&mut file, &mut hasher).map_err(JdkError::IoError)?;
+    compare_checksum(expected, &hasher.finish())
+}
+
+/// running hash of a download in the algorithm its checksum was published with
+enum Hasher {
+    Sha256(Sha256),
+    Sha1(Sha1),
+}
+
+impl Hasher {
+    fn new(checksum_type: ChecksumType) -> Self {
+        match checksum_type {
+            ChecksumType::Sha256 => Hasher::Sha256(Sha256::new()),
+            ChecksumType::Sha1 => Hasher::Sha1(Sha1::new()),
+        }
+    }
+
+    /// seeded with the bytes of an earlier attempt, if any
+    fn from_file(path: &Path, checksum_type: ChecksumType) -> Result<Self> {
+        let mut hasher = Self::new(checksum_type);
+        if let Ok(mut file) = std::fs::File::open(path) {
+            std::io::copy(&mut file, &mut hasher).map_err(JdkError::IoError)?;
+        }
+        Ok(hasher)
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Sha256(h) => h.update(data),
+            Hasher::Sha1(h) => h.update(data),
+        }
+    }
+
+    /// start over, the server is sending the whole file again
+    fn reset(&mut self) {
+        match self {
+            Hasher::Sha256(h) => Digest::reset(h),
+            Hasher::Sha1(h) => Digest::reset(h),
+        }
+    }
+
+    fn finish(self) -> String {
+        match self {
+            Hasher::Sha256(h) => to_hex(&h.finalize()),
+            Hasher::Sha1(h) => to_hex(&h.finalize()),
+        }
+    }
+}
+
+impl std::io::Write for Hasher {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn compare_checksum(expected: &str, actual: &str) -> Result<()> {
+    if expected.trim().eq_ignore_ascii_case(actual) {
+        Ok(())
+    } else {
+        Err(JdkError::ChecksumMismatch {
+            expected: expected.trim().to_lowercase(),
+            actual: actual.to_string(),
+        })
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_file_checksum() {
+        let path = std::env::temp_dir().join(format!("jsh-checksum-{}", std::process::id()));
+        std::fs::write(&path, b"hello jsh").unwrap();
+
+        // sha256("hello jsh")
+        let expected = "76f701fa2e110d611e409fe2a5ad2a588816314768ec6f9768565143e58e3124";
+        assert!(verify_file_checksum(&path, expected, ChecksumType::Sha256).is_ok());
+        assert!(verify_file_checksum(&path, &expected.to_uppercase(), ChecksumType::Sha256).is_ok());
+        assert!(matches!(
+            verify_file_checksum(&path, &"0".repeat(64), ChecksumType::Sha256),
+            Err(JdkError::ChecksumMismatch { .. })
+        ));
+        let sha1 = to_hex(&Sha1::digest(b"hello jsh"));
+        assert!(verify_file_checksum(&path, &sha1, ChecksumType::Sha1).is_ok());
+        assert!(verify_file_checksum(&path, &sha1, ChecksumType::Sha256).is_err());
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_download_resumes_part_file() {
+        use std::io::{Read, Write};
+
+        let body: Vec<u8> = (0..4096u32).map(|i| (i % 251) as u8).collect();
+        let server = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = server.local_addr().unwrap();
+        let served = body.clone();
+        std::thread::spawn(move || {
+            let (mut stream, _) = server.accept().unwrap();
+            let mut buf = [0u8; 2048];
+            let n = stream.read(&mut buf).unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]).to_lowercase();
+            // only answer the resumed request, a full download would hide the bug
+            let start: usize = request
+                .split("range: bytes=")
+                .nth(1)
+                .and_then(|r| r.split('-').next())
+                .and_then(|r| r.parse().ok())
+                .unwrap();
+            let head = format!(
+                "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {}-{}/{}\r\nConnection: close\r\n\r\n",
+                served.len() - start,
+                start,
+                served.len() - 1,
+                served.len()
+            );
+            stream.write_all(head.as_bytes()).unwrap();
+            stream.write_all(&served[start..]).unwrap();
+        });
+
+        let dir = std::env::temp_dir().join(format!("jsh-resume-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("jdk.tar.gz.part"), &body[..1000]).unwrap();
+        let downloader = Downloader {
+            client: Client::new(),
+            download_dir: dir.clone(),
+        };
+
+        // the part file's bytes are hashed before the rest streams in
+        let checksum = to_hex(&Sha256::digest(&body));
+        let path = downloader
+            .download_file(
+                &format!("http://{}/jdk.tar.gz", addr),
+                "jdk.tar.gz",
+                Some((&checksum, ChecksumType::Sha256)),
+                Some(body.len() as u64),
+                |_, _| {},
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(path, dir.join("jdk.tar.gz"));
+        assert_eq!(std::fs::read(&path).unwrap(), body);
+        assert!(!dir.join("jdk.tar.gz.part").exists());
+
+        std::fs::remove_dir_all(&dir).ok();
     }
 }
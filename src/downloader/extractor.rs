@@ -1,7 +1,76 @@
 use crate::error::{JdkError, Result};
-use std::fs;
+use std::fs::{self, File};
+use std::io::Read;
 use std::path::{Path, PathBuf};
 
+/// archive formats the extractor can unpack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    TarGz,
+    TarXz,
+    TarZst,
+    Tar,
+}
+
+impl ArchiveFormat {
+    /// detect format from the file content, falling back to the file name
+    pub fn detect(archive_path: &Path) -> Result<Self> {
+        let mut header = Vec::with_capacity(512);
+        File::open(archive_path)
+            .map_err(JdkError::IoError)?
+            .take(512)
+            .read_to_end(&mut header)
+            .map_err(JdkError::IoError)?;
+
+        if let Some(format) = Self::from_magic(&header) {
+            return Ok(format);
+        }
+        let name = archive_path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .ok_or_else(|| JdkError::ExtractionError("Unknown file type".to_string()))?;
+        Self::from_file_name(name).ok_or_else(|| {
+            JdkError::ExtractionError(format!("Unsupported format: {}", name))
+        })
+    }
+
+    /// match the leading magic bytes of an archive
+    pub fn from_magic(header: &[u8]) -> Option<Self> {
+        if header.starts_with(b"PK\x03\x04") || header.starts_with(b"PK\x05\x06") {
+            Some(Self::Zip)
+        } else if header.starts_with(&[0x1f, 0x8b]) {
+            Some(Self::TarGz)
+        } else if header.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+            Some(Self::TarXz)
+        } else if header.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Some(Self::TarZst)
+        } else if header.len() >= 262 && &header[257..262] == b"ustar" {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+
+    /// match compound suffixes such as `.tar.gz`, case insensitive
+    pub fn from_file_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar.xz") || name.ends_with(".txz") {
+            Some(Self::TarXz)
+        } else if name.ends_with(".tar.zst") || name.ends_with(".tzst") {
+            Some(Self::TarZst)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+}
+
 pub struct Extractor;
 
 impl Extractor {
@@ -9,29 +78,58 @@ impl Extractor {
         Self
     }
 
-    /// extract file to target dir
+    /// extract file to target dir, returns the JDK home found in it
     pub fn extract(&self, archive_path: &Path, target_dir: &Path) -> Result<PathBuf> {
-        fs::create_dir_all(target_dir).map_err(|e| JdkError::IoError(e))?;
-        let extension = archive_path
-            .extension()
-            .and_then(|s| s.to_str())
-            .ok_or_else(|| JdkError::ExtractionError("Unknown file type".to_string()))?;
-        match extension {
-            "zip" => self.extract_zip(archive_path, target_dir),
-            "tar" => self.extract_tar_gz(archive_path, target_dir),
-            _ => Err(JdkError::ExtractionError(format!(
-                "Unsupported format: {}",
-                extension
-            ))),
+        self.unpack(archive_path, target_dir)?;
+        self.find_jdk_root(target_dir)
+    }
+
+    /// extract a JDK into its own directory under `jdks_dir`, returns its home.
+    /// staged in an empty dir first, so JDKs already in `jdks_dir` are not taken for the new one
+    pub fn install(&self, archive: &Path, jdks_dir: &Path) -> Result<PathBuf> {
+        let staging = jdks_dir.join(format!(".install-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&staging);
+
+        let result = self.extract(archive, &staging).and_then(|root| {
+            let relative = root.strip_prefix(&staging).unwrap_or(Path::new("")).to_path_buf();
+            let mut components = relative.components();
+            // archives without a top-level directory are named after the archive
+            let (from, to) = match components.next() {
+                Some(top) => (staging.join(top), jdks_dir.join(top)),
+                None => (staging.clone(), jdks_dir.join(archive_stem(archive))),
+            };
+            if to.exists() {
+                return Err(JdkError::InvalidPath(format!(
+                    "{} already exists, remove it first with 'jsh remove'",
+                    to.display()
+                )));
+            }
+            fs::rename(&from, &to)?;
+
+            let rest = components.as_path();
+            Ok(if rest.as_os_str().is_empty() { to } else { to.join(rest) })
+        });
+        fs::remove_dir_all(&staging).ok();
+        result
+    }
+
+    /// extract file to target dir as it is, for archives that are not a JDK (debug images, sources)
+    pub fn unpack(&self, archive_path: &Path, target_dir: &Path) -> Result<()> {
+        fs::create_dir_all(target_dir).map_err(JdkError::IoError)?;
+        match ArchiveFormat::detect(archive_path)? {
+            ArchiveFormat::Zip => self.extract_zip(archive_path, target_dir),
+            ArchiveFormat::TarGz => self.extract_tar_gz(archive_path, target_dir),
+            ArchiveFormat::TarXz => self.extract_tar_xz(archive_path, target_dir),
+            ArchiveFormat::TarZst => self.extract_tar_zst(archive_path, target_dir),
+            ArchiveFormat::Tar => self.extract_tar(archive_path, target_dir),
         }
     }
 
-    fn extract_zip(&self, archive_path: &Path, target_dir: &Path) -> Result<PathBuf> {
-        use std::fs::File;
+    fn extract_zip(&self, archive_path: &Path, target_dir: &Path) -> Result<()> {
         use std::io;
         use zip::ZipArchive;
 
-        let file = File::open(archive_path).map_err(|e| JdkError::IoError(e))?;
+        let file = File::open(archive_path).map_err(JdkError::IoError)?;
         let mut archive =
             ZipArchive::new(file).map_err(|e| JdkError::ExtractionError(e.to_string()))?;
 
@@ -44,13 +142,13 @@ impl Extractor {
                 None => continue,
             };
             if file.is_dir() {
-                fs::create_dir_all(&outpath).map_err(|e| JdkError::IoError(e))?;
+                fs::create_dir_all(&outpath).map_err(JdkError::IoError)?;
             } else {
                 if let Some(parent) = outpath.parent() {
-                    fs::create_dir_all(parent).map_err(|e| JdkError::IoError(e))?;
+                    fs::create_dir_all(parent).map_err(JdkError::IoError)?;
                 }
-                let mut outfile = File::create(&outpath).map_err(|e| JdkError::IoError(e))?;
-                io::copy(&mut file, &mut outfile).map_err(|e| JdkError::IoError(e))?;
+                let mut outfile = File::create(&outpath).map_err(JdkError::IoError)?;
+                io::copy(&mut file, &mut outfile).map_err(JdkError::IoError)?;
 
                 #[cfg(unix)]
                 {
@@ -61,29 +159,48 @@ impl Extractor {
                 }
             }
         }
-        self.find_jdk_root(target_dir)
+        Ok(())
     }
 
-    fn extract_tar_gz(&self, archive_path: &Path, target_dir: &Path) -> Result<PathBuf> {
+    fn extract_tar_gz(&self, archive_path: &Path, target_dir: &Path) -> Result<()> {
         use flate2::read::GzDecoder;
-        use tar::Archive;
-        use std::fs::File;
 
-        let file = File::open(archive_path)
-            .map_err(|e| JdkError::IoError(e))?;
-        let gz = GzDecoder::new(file);
-        let mut archive = Archive::new(gz);
+        let file = File::open(archive_path).map_err(JdkError::IoError)?;
+        self.unpack_tar(GzDecoder::new(file), target_dir)
+    }
 
-        archive.unpack(target_dir)
-            .map_err(|e| JdkError::ExtractionError(e.to_string()))?;
+    fn extract_tar_xz(&self, archive_path: &Path, target_dir: &Path) -> Result<()> {
+        use xz2::read::XzDecoder;
 
-        self.find_jdk_root(target_dir)
+        let file = File::open(archive_path).map_err(JdkError::IoError)?;
+        self.unpack_tar(XzDecoder::new(file), target_dir)
+    }
+
+    fn extract_tar_zst(&self, archive_path: &Path, target_dir: &Path) -> Result<()> {
+        use zstd::stream::read::Decoder;
+
+        let file = File::open(archive_path).map_err(JdkError::IoError)?;
+        let zst = Decoder::new(file).map_err(|e| JdkError::ExtractionError(e.to_string()))?;
+        self.unpack_tar(zst, target_dir)
+    }
+
+    fn extract_tar(&self, archive_path: &Path, target_dir: &Path) -> Result<()> {
+        let file = File::open(archive_path).map_err(JdkError::IoError)?;
+        self.unpack_tar(file, target_dir)
+    }
+
+    fn unpack_tar<R: Read>(&self, reader: R, target_dir: &Path) -> Result<()> {
+        use tar::Archive;
+
+        let mut archive = Archive::new(reader);
+        archive.unpack(target_dir)
+            .map_err(|e| JdkError::ExtractionError(e.to_string()))
     }
 
     fn find_jdk_root(&self, base_dir: &Path) -> Result<PathBuf> {
         use walkdir::WalkDir;
         for entry in WalkDir::new(base_dir).max_depth(3) {
-            let entry = entry.map_err(|e| JdkError::IoError(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
+            let entry = entry.map_err(|e| JdkError::IoError(std::io::Error::other(e)))?;
             let path = entry.path();
 
             if path.is_dir() {
@@ -100,8 +217,246 @@ impl Extractor {
         }
         Err(JdkError::ExtractionError("JDK root directory not found in archive".to_string()))
     }
+}
 
-
+/// archive file name without its archive extension
+fn archive_stem(archive: &Path) -> String {
+    let name = archive
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "jdk".to_string());
+    [".tar.gz", ".tgz", ".tar.xz", ".tar.zst", ".tar", ".zip"]
+        .iter()
+        .find_map(|ext| name.strip_suffix(ext))
+        .unwrap_or(&name)
+        .to_string()
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const JAVA_BIN: &str = if cfg!(target_os = "windows") { "java.exe" } else { "java" };
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("jsh-extract-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn tar_bytes() -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        let content = b"#!/bin/sh\n";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, format!("jdk-17/bin/{}", JAVA_BIN), &content[..])
+            .unwrap();
+        builder.into_inner().unwrap()
+    }
+
+    fn zip_bytes() -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        writer
+            .start_file(format!("jdk-17/bin/{}", JAVA_BIN), zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"#!/bin/sh\n").unwrap();
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn archive_bytes(format: ArchiveFormat) -> Vec<u8> {
+        match format {
+            ArchiveFormat::Zip => zip_bytes(),
+            ArchiveFormat::Tar => tar_bytes(),
+            ArchiveFormat::TarGz => {
+                let mut enc = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                enc.write_all(&tar_bytes()).unwrap();
+                enc.finish().unwrap()
+            }
+            ArchiveFormat::TarXz => {
+                let mut enc = xz2::write::XzEncoder::new(Vec::new(), 6);
+                enc.write_all(&tar_bytes()).unwrap();
+                enc.finish().unwrap()
+            }
+            ArchiveFormat::TarZst => zstd::stream::encode_all(&tar_bytes()[..], 0).unwrap(),
+        }
+    }
+
+    const ALL_FORMATS: [ArchiveFormat; 5] = [
+        ArchiveFormat::Zip,
+        ArchiveFormat::TarGz,
+        ArchiveFormat::TarXz,
+        ArchiveFormat::TarZst,
+        ArchiveFormat::Tar,
+    ];
+
+    #[test]
+    fn test_from_magic() {
+        for format in ALL_FORMATS {
+            assert_eq!(ArchiveFormat::from_magic(&archive_bytes(format)), Some(format));
+        }
+        assert_eq!(ArchiveFormat::from_magic(b"not an archive"), None);
+    }
+
+    #[test]
+    fn test_from_file_name() {
+        let cases = [
+            ("jdk-17-temurin.zip", Some(ArchiveFormat::Zip)),
+            ("jdk-17-temurin.tar.gz", Some(ArchiveFormat::TarGz)),
+            ("OpenJDK17U.TGZ", Some(ArchiveFormat::TarGz)),
+            ("jdk-17.tar.xz", Some(ArchiveFormat::TarXz)),
+            ("jdk-17.txz", Some(ArchiveFormat::TarXz)),
+            ("jdk-17.tar.zst", Some(ArchiveFormat::TarZst)),
+            ("jdk-17.tzst", Some(ArchiveFormat::TarZst)),
+            ("jdk-17.tar", Some(ArchiveFormat::Tar)),
+            ("jdk-17.gz", None),
+            ("jdk-17.msi", None),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(ArchiveFormat::from_file_name(name), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_detect_prefers_content_over_name() {
+        let dir = test_dir("detect");
+        // a gzip stream saved with a misleading suffix
+        let path = dir.join("jdk-17.zip");
+        fs::write(&path, archive_bytes(ArchiveFormat::TarGz)).unwrap();
+        assert_eq!(ArchiveFormat::detect(&path).unwrap(), ArchiveFormat::TarGz);
+
+        let path = dir.join("jdk-17.msi");
+        fs::write(&path, b"not an archive").unwrap();
+        assert!(matches!(
+            ArchiveFormat::detect(&path),
+            Err(JdkError::ExtractionError(_))
+        ));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_extract_all_formats() {
+        for format in ALL_FORMATS {
+            let dir = test_dir(&format!("{:?}", format).to_lowercase());
+            // same name for every format, so only content decides
+            let archive = dir.join("jdk-17-temurin.tar.gz");
+            fs::write(&archive, archive_bytes(format)).unwrap();
+
+            let root = Extractor::new().extract(&archive, &dir.join("jdks")).unwrap();
+            assert_eq!(root, dir.join("jdks").join("jdk-17"), "{:?}", format);
+            assert!(root.join("bin").join(JAVA_BIN).is_file());
+            fs::remove_dir_all(&dir).ok();
+        }
+    }
+
+    /// write a .tar.gz holding one small file per entry
+    fn write_tar_gz(path: &Path, entries: &[String]) {
+        let mut builder = tar::Builder::new(Vec::new());
+        for entry in entries {
+            let content = b"#!/bin/sh\n";
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder.append_data(&mut header, entry, &content[..]).unwrap();
+        }
+        let mut enc = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(&builder.into_inner().unwrap()).unwrap();
+        fs::write(path, enc.finish().unwrap()).unwrap();
+    }
+
+    fn entries(jdks_dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(jdks_dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_archive_stem() {
+        assert_eq!(archive_stem(Path::new("/tmp/OpenJDK17U-jdk_x64_linux.tar.gz")), "OpenJDK17U-jdk_x64_linux");
+        assert_eq!(archive_stem(Path::new("zulu17.46.19-ca-jdk17.0.9-linux_x64.tgz")), "zulu17.46.19-ca-jdk17.0.9-linux_x64");
+        assert_eq!(archive_stem(Path::new("C:/Downloads/jdk-21_windows-x64_bin.zip")), "jdk-21_windows-x64_bin");
+        assert_eq!(archive_stem(Path::new("jdk-21")), "jdk-21");
+    }
+
+    #[test]
+    fn test_install() {
+        let dir = test_dir("install");
+        let jdks_dir = dir.join("jdks");
+        let archive = dir.join("jdk.tar.gz");
+        write_tar_gz(&archive, &[format!("jdk-17.0.9+9/bin/{}", JAVA_BIN)]);
+
+        let home = Extractor::new().install(&archive, &jdks_dir).unwrap();
+        assert_eq!(home, jdks_dir.join("jdk-17.0.9+9"));
+        assert!(home.join("bin").join(JAVA_BIN).is_file());
+        assert_eq!(entries(&jdks_dir), vec!["jdk-17.0.9+9"]);
+
+        // a second install of the same build must not overwrite the first one
+        let err = Extractor::new().install(&archive, &jdks_dir).unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{}", err);
+        assert!(home.join("bin").join(JAVA_BIN).is_file());
+        assert_eq!(entries(&jdks_dir), vec!["jdk-17.0.9+9"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_install_keeps_nested_home() {
+        let dir = test_dir("install-nested");
+        let jdks_dir = dir.join("jdks");
+        let archive = dir.join("jdk-mac.tar.gz");
+        write_tar_gz(&archive, &[format!("jdk-21.jdk/Contents/Home/bin/{}", JAVA_BIN)]);
+
+        let home = Extractor::new().install(&archive, &jdks_dir).unwrap();
+        assert_eq!(home, jdks_dir.join("jdk-21.jdk/Contents/Home"));
+        assert_eq!(entries(&jdks_dir), vec!["jdk-21.jdk"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_install_without_top_level_dir() {
+        let dir = test_dir("install-flat");
+        let jdks_dir = dir.join("jdks");
+
+        // files at the archive root are moved into a directory named after the archive
+        let flat = dir.join("custom-jdk-17.tgz");
+        write_tar_gz(&flat, &[format!("bin/{}", JAVA_BIN), "release".to_string()]);
+        let home = Extractor::new().install(&flat, &jdks_dir).unwrap();
+        assert_eq!(home, jdks_dir.join("custom-jdk-17"));
+        assert!(home.join("release").is_file());
+
+        // only the directory holding the JDK is kept when there are several at the top
+        let mixed = dir.join("bundle.tar.gz");
+        write_tar_gz(&mixed, &["docs/README".to_string(), format!("jdk-21/bin/{}", JAVA_BIN)]);
+        assert_eq!(Extractor::new().install(&mixed, &jdks_dir).unwrap(), jdks_dir.join("jdk-21"));
+        assert_eq!(entries(&jdks_dir), vec!["custom-jdk-17", "jdk-21"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 
+    #[test]
+    fn test_install_cleans_up_on_failure() {
+        let dir = test_dir("install-failure");
+        let jdks_dir = dir.join("jdks");
+
+        let no_jdk = dir.join("docs.tar.gz");
+        write_tar_gz(&no_jdk, &["docs/README".to_string()]);
+        assert!(matches!(Extractor::new().install(&no_jdk, &jdks_dir), Err(JdkError::ExtractionError(_))));
+
+        let corrupt = dir.join("broken.tar.gz");
+        fs::write(&corrupt, [0x1f, 0x8b, 0x08, 0x00, 0xde, 0xad]).unwrap();
+        assert!(Extractor::new().install(&corrupt, &jdks_dir).is_err());
+
+        assert!(entries(&jdks_dir).is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,67 @@
+use crate::downloader::cache;
+use crate::downloader::mirror;
+use crate::error::Result;
+use reqwest::Client;
+use serde::Deserialize;
+
+// ============github release api structs============
+#[derive(Deserialize, Debug)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub prerelease: bool,
+    pub assets: Vec<Asset>,
+}
+#[derive(Deserialize, Debug)]
+pub struct Asset {
+    pub name: String,
+    pub browser_download_url: String,
+    pub size: u64,
+}
+// ============github release api structs============
+
+/// Client for the GitHub API, which rejects requests without a user agent
+pub fn client() -> Client {
+    Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .user_agent(concat!("jsh/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .unwrap()
+}
+
+/// most releases the GitHub API returns per request
+const PAGE_SIZE: usize = 100;
+
+/// Published releases of `owner/repo`, newest first, across all pages
+pub async fn fetch_releases(client: &Client, repo: &str) -> Result<Vec<Release>> {
+    let mut releases = Vec::new();
+    for page in 1.. {
+        let url = format!(
+            "https://api.github.com/repos/{}/releases?per_page={}&page={}",
+            repo, PAGE_SIZE, page
+        );
+        let batch: Vec<Release> = cache::get_json(client.get(&url)).await?;
+        let count = batch.len();
+        releases.extend(batch.into_iter().filter(|r| !r.draft && !r.prerelease));
+        // the last page is the first one that is not full, pages past it are empty
+        if count < PAGE_SIZE {
+            break;
+        }
+    }
+    Ok(releases)
+}
+
+/// First hash in a checksum file ("<sha256>  <file name>")
+pub async fn fetch_checksum(client: &Client, url: &str) -> Option<String> {
+    let text = mirror::send(client.get(url))
+        .await
+        .ok()?
+        .error_for_status()
+        .ok()?
+        .text()
+        .await
+        .ok()?;
+    text.split_whitespace().next().map(|h| h.to_lowercase())
+}
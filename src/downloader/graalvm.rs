@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+use crate::downloader::github::{self, Release};
+use crate::downloader::traits::{BundleType, ChecksumType, ImageType, JdkPackage, JdkSource, detect_arch, detect_os, get_file_type, is_lts_version, os_name};
+use crate::error::JdkError;
+use crate::error::Result;
+use reqwest::Client;
+
+const REPO: &str = "graalvm/graalvm-ce-builds";
+
+pub struct GraalVmSource {
+    client: Client,
+}
+
+impl GraalVmSource {
+    pub fn new() -> Self {
+        Self {
+            client: github::client(),
+        }
+    }
+}
+
+/// Newest release per major version, releases are listed newest first
+fn latest_packages(releases: Vec<Release>, os: &str, arch: &str) -> Vec<JdkPackage> {
+    let file_type = get_file_type(os);
+    let suffix = format!("_{}-{}_bin.{}", os_name(os), arch, file_type);
+    let mut packages: Vec<JdkPackage> = Vec::new();
+
+    for release in releases {
+        // older "vm-22.3.x" tags use a different layout and are skipped
+        let Some(version) = release.tag_name.strip_prefix("jdk-") else {
+            continue;
+        };
+        let Some(major_version) = version.split('.').next().and_then(|m| m.parse().ok()) else {
+            continue;
+        };
+        if packages.iter().any(|p| p.major_version == major_version) {
+            continue;
+        }
+        let Some(asset) = release
+            .assets
+            .into_iter()
+            .find(|a| a.name.starts_with("graalvm-community-jdk-") && a.name.ends_with(&suffix))
+        else {
+            continue;
+        };
+
+        packages.push(JdkPackage {
+            version: version.to_string(),
+            major_version,
+            vendor: "graalvm".to_string(),
+            os: os.to_string(),
+            arch: arch.to_string(),
+            download_url: asset.browser_download_url,
+            size: asset.size,
+            file_type: file_type.to_string(),
+            is_lts: is_lts_version(major_version),
+            checksum: None,
+            checksum_type: ChecksumType::Sha256,
+            bundle_type: BundleType::Standard,
+            image_type: ImageType::Jdk,
+            jvm_impl: "graal".to_string(),
+        });
+    }
+    packages
+}
+
+#[async_trait]
+impl JdkSource for GraalVmSource {
+    fn name(&self) -> &str {
+        "GraalVM Community"
+    }
+
+    async fn fetch_version(&self) -> Result<Vec<JdkPackage>> {
+        let releases = github::fetch_releases(&self.client, REPO).await?;
+        Ok(latest_packages(releases, &detect_os(), &detect_arch()))
+    }
+
+    async fn find_package(&self, major_version: u32) -> Result<JdkPackage> {
+        let mut package = self
+            .fetch_version()
+            .await?
+            .into_iter()
+            .find(|p| p.major_version == major_version)
+            .ok_or_else(|| JdkError::PackageNotFound(major_version.to_string()))?;
+        // published next to every asset as `<asset>.sha256`
+        let checksum_url = format!("{}.sha256", package.download_url);
+        package.checksum = github::fetch_checksum(&self.client, &checksum_url).await;
+        Ok(package)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_packages() {
+        let asset = |version: &str, platform: &str| {
+            format!(
+                r#"{{"name": "graalvm-community-jdk-{v}_{p}_bin.tar.gz", "size": 301989888,
+                "browser_download_url": "https://github.com/graalvm/graalvm-ce-builds/releases/download/jdk-{v}/graalvm-community-jdk-{v}_{p}_bin.tar.gz"}}"#,
+                v = version,
+                p = platform
+            )
+        };
+        let json = format!(
+            r#"[
+                {{"tag_name": "jdk-21.0.1", "assets": [{}, {}]}},
+                {{"tag_name": "jdk-21.0.0", "assets": [{}]}},
+                {{"tag_name": "jdk-17.0.9", "assets": [{}]}},
+                {{"tag_name": "vm-22.3.3", "assets": []}}
+            ]"#,
+            asset("21.0.1", "macos-aarch64"),
+            asset("21.0.1", "linux-x64"),
+            asset("21.0.0", "linux-x64"),
+            asset("17.0.9", "linux-x64"),
+        );
+        let releases: Vec<Release> = serde_json::from_str(&json).unwrap();
+
+        let packages = latest_packages(releases, "linux", "x64");
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].version, "21.0.1");
+        assert!(packages[0].download_url.ends_with("graalvm-community-jdk-21.0.1_linux-x64_bin.tar.gz"));
+        assert_eq!(packages[1].major_version, 17);
+        assert!(packages[1].is_lts);
+    }
+}
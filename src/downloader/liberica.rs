@@ -0,0 +1,184 @@
+use async_trait::async_trait;
+use crate::downloader::cache;
+use crate::downloader::traits::{BundleType, ChecksumType, ImageType, JdkPackage, JdkSource, detect_arch, detect_os, get_file_type, os_name};
+use crate::error::JdkError;
+use crate::error::Result;
+use reqwest::Client;
+use serde::Deserialize;
+
+const API_URL: &str = "https://api.bell-sw.com/v1/liberica/releases";
+
+pub struct LibericaSource {
+    client: Client,
+}
+
+impl LibericaSource {
+    pub fn new() -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// latest release of every feature version for this platform,
+    /// optionally limited to one major version and bundle
+    async fn fetch_releases(&self, major_version: Option<u32>, bundle: Option<BundleType>) -> Result<Vec<JdkPackage>> {
+        let os = detect_os();
+        let file_type = get_file_type(&os);
+        let arch = detect_arch();
+        let (arch, bitness) = liberica_arch(&arch);
+        let mut query = vec![
+            ("os", os_name(&os).to_string()),
+            ("arch", arch.to_string()),
+            ("bitness", bitness.to_string()),
+            ("package-type", file_type.to_string()),
+            ("installation-type", "archive".to_string()),
+            ("release-type", "all".to_string()),
+            ("version-modifier", "latest".to_string()),
+        ];
+        if let Some(major) = major_version {
+            query.push(("version-feature", major.to_string()));
+        }
+        if let Some(bundle) = bundle {
+            query.push(("bundle-type", bundle_query(bundle).to_string()));
+        }
+
+        let response: Vec<LibericaRelease> = cache::get_json(self.client.get(API_URL).query(&query)).await?;
+
+        let mut packages: Vec<JdkPackage> = response
+            .into_iter()
+            .filter_map(|release| release.into_package(&os, file_type))
+            .collect();
+        packages.sort_by_key(|p| (std::cmp::Reverse(p.major_version), p.bundle_type as u8));
+        Ok(packages)
+    }
+}
+
+/// BellSoft splits the arch into family and bitness
+fn liberica_arch(arch: &str) -> (&str, u32) {
+    match arch {
+        "x64" => ("x86", 64),
+        "aarch64" => ("arm", 64),
+        "arm" => ("arm", 32),
+        "ppc64le" => ("ppc", 64),
+        "riscv64" => ("riscv", 64),
+        other => (other, 64),
+    }
+}
+
+fn bundle_query(bundle: BundleType) -> &'static str {
+    match bundle {
+        BundleType::Standard => "jdk",
+        BundleType::Full => "jdk-full",
+        BundleType::Lite => "jdk-lite",
+    }
+}
+
+// ============bellsoft api structs============
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct LibericaRelease {
+    version: String,
+    feature_version: u32,
+    download_url: String,
+    size: u64,
+    bundle_type: String,
+    #[serde(default)]
+    sha1: Option<String>,
+    #[serde(rename = "LTS", default)]
+    lts: bool,
+    #[serde(rename = "GA", default)]
+    ga: bool,
+}
+
+impl LibericaRelease {
+    /// JDK bundles only, JREs and CRaC builds share the endpoint
+    fn into_package(self, os: &str, file_type: &str) -> Option<JdkPackage> {
+        let bundle_type = match self.bundle_type.as_str() {
+            "jdk" => BundleType::Standard,
+            "jdk-full" => BundleType::Full,
+            "jdk-lite" => BundleType::Lite,
+            _ => return None,
+        };
+        if !self.ga {
+            return None;
+        }
+        Some(JdkPackage {
+            version: self.version,
+            major_version: self.feature_version,
+            vendor: "liberica".to_string(),
+            os: os.to_string(),
+            arch: detect_arch(),
+            download_url: self.download_url,
+            size: self.size,
+            file_type: file_type.to_string(),
+            is_lts: self.lts,
+            // BellSoft only publishes SHA-1 sums
+            checksum: self.sha1.filter(|c| !c.is_empty()),
+            checksum_type: ChecksumType::Sha1,
+            bundle_type,
+            image_type: ImageType::Jdk,
+            jvm_impl: "hotspot".to_string(),
+        })
+    }
+}
+// ============bellsoft api structs============
+
+#[async_trait]
+impl JdkSource for LibericaSource {
+    fn name(&self) -> &str {
+        "BellSoft Liberica"
+    }
+
+    async fn fetch_version(&self) -> Result<Vec<JdkPackage>> {
+        self.fetch_releases(None, None).await
+    }
+
+    async fn find_package(&self, major_version: u32) -> Result<JdkPackage> {
+        self.find_bundle(major_version, BundleType::Standard).await
+    }
+
+    async fn find_bundle(&self, major_version: u32, bundle: BundleType) -> Result<JdkPackage> {
+        self.fetch_releases(Some(major_version), Some(bundle))
+            .await?
+            .into_iter()
+            .find(|p| p.major_version == major_version && p.bundle_type == bundle)
+            .ok_or_else(|| JdkError::PackageNotFound(format!("{} ({} bundle)", major_version, bundle)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_package() {
+        let json = r#"[{
+            "bitness": 64, "latestLTS": true, "updateVersion": 9, "featureVersion": 17,
+            "downloadUrl": "https://download.bell-sw.com/java/17.0.9+11/bellsoft-jdk17.0.9+11-linux-amd64-full.tar.gz",
+            "latestInFeatureVersion": true, "LTS": true, "bundleType": "jdk-full", "packageType": "tar.gz",
+            "FX": true, "GA": true, "architecture": "x86", "os": "linux", "version": "17.0.9+11",
+            "sha1": "3c6f2a1b", "filename": "bellsoft-jdk17.0.9+11-linux-amd64-full.tar.gz",
+            "installationType": "archive", "size": 211876352
+        }, {
+            "featureVersion": 17, "LTS": true, "GA": true, "bundleType": "jre", "version": "17.0.9+11",
+            "downloadUrl": "https://download.bell-sw.com/java/17.0.9+11/bellsoft-jre17.0.9+11-linux-amd64.tar.gz",
+            "size": 45088768
+        }]"#;
+        let releases: Vec<LibericaRelease> = serde_json::from_str(json).unwrap();
+        let packages: Vec<JdkPackage> = releases
+            .into_iter()
+            .filter_map(|r| r.into_package("linux", "tar.gz"))
+            .collect();
+
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].version, "17.0.9+11");
+        assert_eq!(packages[0].bundle_type, BundleType::Full);
+        assert_eq!(packages[0].vendor, "liberica");
+        assert_eq!(packages[0].checksum.as_deref(), Some("3c6f2a1b"));
+        assert_eq!(packages[0].checksum_type, ChecksumType::Sha1);
+        assert!(packages[0].is_lts);
+    }
+}
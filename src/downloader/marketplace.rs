@@ -0,0 +1,145 @@
+use async_trait::async_trait;
+use crate::downloader::cache;
+use crate::downloader::traits::{
+    BundleType, ChecksumType, ImageType, JdkPackage, JdkSource, MAX_CONCURRENT_REQUESTS, detect_arch, detect_os, get_file_type, is_lts_version,
+};
+use crate::error::JdkError;
+use crate::error::Result;
+use futures_util::{StreamExt, stream};
+use reqwest::Client;
+use serde::Deserialize;
+
+/// Vendors publishing release metadata through the Adoptium marketplace API
+const API_URL: &str = "https://marketplace-api.adoptium.net/v1";
+
+pub struct MarketplaceSource {
+    client: Client,
+    /// marketplace vendor id
+    vendor: &'static str,
+    /// vendor name used in JdkPackage (eg: "semeru")
+    package_vendor: &'static str,
+    name: &'static str,
+    /// JVM implementation when the asset does not name one
+    jvm_impl: &'static str,
+}
+
+impl MarketplaceSource {
+    fn new(vendor: &'static str, package_vendor: &'static str, name: &'static str, jvm_impl: &'static str) -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+            vendor,
+            package_vendor,
+            name,
+            jvm_impl,
+        }
+    }
+
+    pub fn microsoft() -> Self {
+        Self::new("microsoft", "microsoft", "Microsoft Build of OpenJDK", "hotspot")
+    }
+
+    /// IBM Semeru Runtime Open Edition, built with the OpenJ9 JVM
+    pub fn semeru() -> Self {
+        Self::new("ibm", "semeru", "IBM Semeru (OpenJ9)", "openj9")
+    }
+
+    /// latest build of a feature version for this platform
+    async fn fetch_version_package(&self, version: u32, is_lts: bool, image: ImageType) -> Result<JdkPackage> {
+        let os = detect_os();
+        let url = format!("{}/assets/latest/{}/{}", API_URL, self.vendor, version);
+        let request = self.client.get(&url).query(&[
+            ("os", os.as_str()),
+            ("architecture", detect_arch().as_str()),
+            ("image_type", &image.to_string()),
+        ]);
+        let mut response: Vec<MarketplaceAsset> = cache::get_json(request).await?;
+
+        let file_type = get_file_type(&os);
+        response.retain(|a| a.binary.package.link.ends_with(file_type));
+        let asset = response
+            .pop()
+            .ok_or_else(|| JdkError::PackageNotFound(version.to_string()))?;
+
+        Ok(JdkPackage {
+            version: asset.version.openjdk_version,
+            major_version: asset.version.major,
+            vendor: self.package_vendor.to_string(),
+            os: asset.binary.os,
+            arch: asset.binary.architecture,
+            download_url: asset.binary.package.link,
+            size: asset.binary.package.size,
+            file_type: file_type.to_string(),
+            is_lts,
+            checksum: asset.binary.package.sha256sum,
+            checksum_type: ChecksumType::Sha256,
+            bundle_type: BundleType::Standard,
+            image_type: image,
+            jvm_impl: asset.binary.jvm_impl.unwrap_or_else(|| self.jvm_impl.to_string()),
+        })
+    }
+}
+
+// ============marketplace api structs============
+#[derive(Deserialize, Debug)]
+struct AvailableReleases {
+    available_releases: Vec<u32>,
+    available_lts_releases: Vec<u32>,
+}
+#[derive(Deserialize, Debug)]
+struct MarketplaceAsset {
+    binary: Binary,
+    version: Version,
+}
+#[derive(Deserialize, Debug)]
+struct Version {
+    major: u32,
+    openjdk_version: String,
+}
+#[derive(Deserialize, Debug)]
+struct Binary {
+    architecture: String,
+    os: String,
+    #[serde(default)]
+    jvm_impl: Option<String>,
+    package: Package,
+}
+#[derive(Deserialize, Debug)]
+struct Package {
+    link: String,
+    size: u64,
+    #[serde(default, alias = "checksum")]
+    sha256sum: Option<String>,
+}
+// ============marketplace api structs============
+
+#[async_trait]
+impl JdkSource for MarketplaceSource {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    async fn fetch_version(&self) -> Result<Vec<JdkPackage>> {
+        let url = format!("{}/info/available_releases/{}", API_URL, self.vendor);
+        let releases: AvailableReleases = cache::get_json(self.client.get(&url)).await?;
+
+        let lts = &releases.available_lts_releases;
+        let packages = stream::iter(releases.available_releases.iter().rev().copied())
+            .map(|version| self.fetch_version_package(version, lts.contains(&version), ImageType::Jdk))
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .filter_map(|pkg| async move { pkg.ok() })
+            .collect()
+            .await;
+        Ok(packages)
+    }
+
+    async fn find_package(&self, major_version: u32) -> Result<JdkPackage> {
+        self.find_image(major_version, ImageType::Jdk).await
+    }
+
+    async fn find_image(&self, major_version: u32, image: ImageType) -> Result<JdkPackage> {
+        self.fetch_version_package(major_version, is_lts_version(major_version), image).await
+    }
+}
@@ -0,0 +1,142 @@
+use crate::error::{JdkError, Result};
+use colored::Colorize;
+use reqwest::{RequestBuilder, Response, Url};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// `mirrors` from config.json, set once before any source is queried
+static MIRRORS: OnceLock<HashMap<String, Vec<String>>> = OnceLock::new();
+
+/// Use these mirrors for the rest of this run, without them every url is fetched as it is
+pub fn set_mirrors(mirrors: HashMap<String, Vec<String>>) {
+    let _ = MIRRORS.set(mirrors);
+}
+
+/// Urls to try in order for `url`: the longest configured prefix is replaced by each of its mirrors,
+/// urls without a configured prefix are used as they are
+pub fn candidates(url: &str, mirrors: &HashMap<String, Vec<String>>) -> Vec<String> {
+    let matched = mirrors
+        .iter()
+        .map(|(prefix, list)| (prefix.trim_end_matches('/'), list))
+        .filter(|(prefix, list)| {
+            // "https://api.adoptium.net" must not match "https://api.adoptium.network"
+            !list.is_empty()
+                && url
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with(['/', '?']))
+        })
+        .max_by_key(|(prefix, _)| prefix.len());
+
+    match matched {
+        Some((prefix, list)) => {
+            let rest = &url[prefix.len()..];
+            list.iter()
+                .map(|mirror| format!("{}{}", mirror.trim_end_matches('/'), rest))
+                .collect()
+        }
+        None => vec![url.to_string()],
+    }
+}
+
+/// Send a request, moving on to the next mirror of its url when a connection fails
+pub async fn send(request: RequestBuilder) -> Result<Response> {
+    send_with(request, MIRRORS.get_or_init(HashMap::new)).await
+}
+
+async fn send_with(request: RequestBuilder, mirrors: &HashMap<String, Vec<String>>) -> Result<Response> {
+    let (client, request) = request.build_split();
+    let request = request.map_err(|e| JdkError::NetworkError(e.to_string()))?;
+    let urls = candidates(request.url().as_str(), mirrors);
+
+    let mut last_error = None;
+    for (i, url) in urls.iter().enumerate() {
+        let mut attempt = request
+            .try_clone()
+            .ok_or_else(|| JdkError::NetworkError("Request body cannot be sent twice".to_string()))?;
+        *attempt.url_mut() = Url::parse(url)
+            .map_err(|e| JdkError::ConfigError(format!("Invalid mirror url {}: {}", url, e)))?;
+
+        match client.execute(attempt).await {
+            Ok(response) => return Ok(response),
+            Err(e) if e.is_connect() || e.is_timeout() => {
+                if i + 1 < urls.len() {
+                    eprintln!("{}", format!("Cannot reach {}, trying next mirror...", url).yellow());
+                }
+                last_error = Some(e);
+            }
+            Err(e) => return Err(JdkError::NetworkError(e.to_string())),
+        }
+    }
+    Err(JdkError::NetworkError(
+        last_error.map(|e| e.to_string()).unwrap_or_default(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_candidates() {
+        let mut mirrors = HashMap::new();
+        mirrors.insert(
+            "https://api.adoptium.net/".to_string(),
+            vec![
+                "https://mirrors.example.cn/adoptium-api/".to_string(),
+                "https://api.adoptium.net".to_string(),
+            ],
+        );
+        mirrors.insert(
+            "https://github.com/SAP".to_string(),
+            vec!["http://127.0.0.1:8080/sap".to_string()],
+        );
+
+        assert_eq!(
+            candidates("https://api.adoptium.net/v3/info/available_releases?x=1", &mirrors),
+            vec![
+                "https://mirrors.example.cn/adoptium-api/v3/info/available_releases?x=1",
+                "https://api.adoptium.net/v3/info/available_releases?x=1",
+            ]
+        );
+        assert_eq!(
+            candidates("https://github.com/SAP/SapMachine/releases", &mirrors),
+            vec!["http://127.0.0.1:8080/sap/SapMachine/releases"]
+        );
+        assert_eq!(
+            candidates("https://api.adoptium.network/v3", &mirrors),
+            vec!["https://api.adoptium.network/v3"]
+        );
+        assert_eq!(candidates("https://api.azul.com/metadata", &mirrors), vec!["https://api.azul.com/metadata"]);
+    }
+
+    #[tokio::test]
+    async fn test_send_falls_back_to_next_mirror() {
+        // nothing listens on a port that was just released
+        let closed = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        let server = TcpListener::bind("127.0.0.1:0").unwrap();
+        let open = server.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (mut stream, _) = server.accept().unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf);
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok")
+                .unwrap();
+        });
+
+        let mut mirrors = HashMap::new();
+        mirrors.insert(
+            "https://api.adoptium.net".to_string(),
+            vec![format!("http://{}", closed), format!("http://{}", open)],
+        );
+        let client = reqwest::Client::new();
+        let response = send_with(client.get("https://api.adoptium.net/v3/info"), &mirrors)
+            .await
+            .unwrap();
+
+        assert_eq!(response.url().as_str(), format!("http://{}/v3/info", open));
+        assert_eq!(response.text().await.unwrap(), "ok");
+    }
+}
@@ -1,5 +1,73 @@
 pub mod adoptium;
+pub mod cache;
+pub mod corretto;
+pub mod disco;
+pub mod github;
+pub mod graalvm;
+pub mod liberica;
+pub mod marketplace;
+pub mod mirror;
+pub mod sapmachine;
+pub mod zulu;
 pub mod traits;
+#[allow(clippy::module_inception)]
 pub mod downloader;
 pub mod extractor;
 pub mod progress;
+
+use crate::error::{JdkError, Result};
+use adoptium::AdoptiumSource;
+use corretto::CorrettoSource;
+use graalvm::GraalVmSource;
+use liberica::LibericaSource;
+use marketplace::MarketplaceSource;
+use sapmachine::SapMachineSource;
+use traits::JdkSource;
+use zulu::ZuluSource;
+
+/// Vendors `--vendor` accepts, aliases aside
+pub const VENDORS: [&str; 8] = [
+    "temurin", "zulu", "corretto", "graalvm", "liberica", "microsoft", "sapmachine", "semeru",
+];
+
+/// Download source for a vendor name or alias
+pub fn source_for_vendor(vendor: &str) -> Result<Box<dyn JdkSource>> {
+    let source: Box<dyn JdkSource> = match vendor.to_lowercase().as_str() {
+        "temurin" | "adoptium" => Box::new(AdoptiumSource::new()),
+        "zulu" | "azul" => Box::new(ZuluSource::new()),
+        "corretto" | "amzn" => Box::new(CorrettoSource::new()),
+        "graalvm" | "graalce" => Box::new(GraalVmSource::new()),
+        "liberica" | "librca" | "bellsoft" => Box::new(LibericaSource::new()),
+        "microsoft" | "ms" => Box::new(MarketplaceSource::microsoft()),
+        "semeru" | "sem" | "ibm" => Box::new(MarketplaceSource::semeru()),
+        "sapmachine" | "sapmchn" | "sap" => Box::new(SapMachineSource::new()),
+        _ => {
+            return Err(JdkError::UnknownVendor {
+                vendor: vendor.to_string(),
+                supported: VENDORS.join(", "),
+            });
+        }
+    };
+    Ok(source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_for_vendor() {
+        for vendor in VENDORS {
+            assert!(source_for_vendor(vendor).is_ok(), "{}", vendor);
+        }
+        assert!(source_for_vendor("AMZN").is_ok());
+        match source_for_vendor("dragonwell") {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "Unknown vendor 'dragonwell', supported vendors: \
+                 temurin, zulu, corretto, graalvm, liberica, microsoft, sapmachine, semeru"
+            ),
+            Ok(_) => panic!("dragonwell has no download source"),
+        }
+    }
+}
@@ -5,13 +5,19 @@ impl ProgressDisplay {
 
     pub fn simple_callback() -> impl Fn(u64, u64) {
         move |cur, total| {
+            use std::io::{self, Write};
+            // size unknown until the download ends
+            if total == 0 {
+                print!("\r  Progress: {:4} MB", cur / 1024 / 1024);
+                io::stdout().flush().ok();
+                return;
+            }
             let percentage = (cur as f64 / total as f64 * 100.0) as u32;
             let cur_mb = cur / 1024 / 1024;
             let total_mb = total / 1024 / 1024;
             print!("\r  Progress: {:3}% ({:4}/{:4} MB)",
                    percentage, cur_mb, total_mb
             );
-            use std::io::{self, Write};
             io::stdout().flush().ok();
             if cur >= total {
                 println!();
@@ -0,0 +1,137 @@
+use async_trait::async_trait;
+use crate::downloader::github::{self, Release};
+use crate::downloader::traits::{BundleType, ChecksumType, ImageType, JdkPackage, JdkSource, detect_arch, detect_os, get_file_type, is_lts_version, os_name};
+use crate::error::JdkError;
+use crate::error::Result;
+use reqwest::Client;
+
+const REPO: &str = "SAP/SapMachine";
+
+pub struct SapMachineSource {
+    client: Client,
+}
+
+impl SapMachineSource {
+    pub fn new() -> Self {
+        Self {
+            client: github::client(),
+        }
+    }
+}
+
+/// Newest release per major version with the URL of its checksum file
+fn latest_packages(releases: Vec<Release>, os: &str, arch: &str) -> Vec<(JdkPackage, Option<String>)> {
+    let file_type = get_file_type(os);
+    let platform = format!("_{}-{}_bin", os_name(os), arch);
+    let mut packages: Vec<(JdkPackage, Option<String>)> = Vec::new();
+
+    for release in releases {
+        // tags look like "sapmachine-17.0.9", early access builds "sapmachine-22+25" are prereleases
+        let Some(version) = release.tag_name.strip_prefix("sapmachine-") else {
+            continue;
+        };
+        let Some(major_version) = version
+            .split(|c: char| !c.is_ascii_digit())
+            .next()
+            .and_then(|m| m.parse().ok())
+        else {
+            continue;
+        };
+        if packages.iter().any(|(p, _)| p.major_version == major_version) {
+            continue;
+        }
+
+        let stem = format!("sapmachine-jdk-{}{}", version, platform);
+        let archive_name = format!("{}.{}", stem, file_type);
+        let Some(asset) = release.assets.iter().find(|a| a.name == archive_name) else {
+            continue;
+        };
+        let checksum_url = release
+            .assets
+            .iter()
+            .find(|a| a.name == format!("{}.sha256.txt", stem))
+            .map(|a| a.browser_download_url.clone());
+
+        packages.push((
+            JdkPackage {
+                version: version.to_string(),
+                major_version,
+                vendor: "sapmachine".to_string(),
+                os: os.to_string(),
+                arch: arch.to_string(),
+                download_url: asset.browser_download_url.clone(),
+                size: asset.size,
+                file_type: file_type.to_string(),
+                is_lts: is_lts_version(major_version),
+                checksum: None,
+                checksum_type: ChecksumType::Sha256,
+                bundle_type: BundleType::Standard,
+                image_type: ImageType::Jdk,
+                jvm_impl: "hotspot".to_string(),
+            },
+            checksum_url,
+        ));
+    }
+    packages
+}
+
+#[async_trait]
+impl JdkSource for SapMachineSource {
+    fn name(&self) -> &str {
+        "SAP SapMachine"
+    }
+
+    async fn fetch_version(&self) -> Result<Vec<JdkPackage>> {
+        let releases = github::fetch_releases(&self.client, REPO).await?;
+        Ok(latest_packages(releases, &detect_os(), &detect_arch())
+            .into_iter()
+            .map(|(pkg, _)| pkg)
+            .collect())
+    }
+
+    async fn find_package(&self, major_version: u32) -> Result<JdkPackage> {
+        let releases = github::fetch_releases(&self.client, REPO).await?;
+        let (mut package, checksum_url) = latest_packages(releases, &detect_os(), &detect_arch())
+            .into_iter()
+            .find(|(p, _)| p.major_version == major_version)
+            .ok_or_else(|| JdkError::PackageNotFound(major_version.to_string()))?;
+        if let Some(url) = checksum_url {
+            package.checksum = github::fetch_checksum(&self.client, &url).await;
+        }
+        Ok(package)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_packages() {
+        let asset = |name: &str| {
+            format!(
+                r#"{{"name": "{n}", "size": 188743680,
+                "browser_download_url": "https://github.com/SAP/SapMachine/releases/download/x/{n}"}}"#,
+                n = name
+            )
+        };
+        let json = format!(
+            r#"[
+                {{"tag_name": "sapmachine-21.0.1", "assets": [{}, {}, {}]}},
+                {{"tag_name": "sapmachine-17.0.9", "assets": [{}]}}
+            ]"#,
+            asset("sapmachine-jdk-21.0.1_linux-x64_bin.tar.gz"),
+            asset("sapmachine-jdk-21.0.1_linux-x64_bin.sha256.txt"),
+            asset("sapmachine-jre-21.0.1_linux-x64_bin.tar.gz"),
+            asset("sapmachine-jdk-17.0.9_windows-x64_bin.zip"),
+        );
+        let releases: Vec<Release> = serde_json::from_str(&json).unwrap();
+
+        let packages = latest_packages(releases, "linux", "x64");
+        assert_eq!(packages.len(), 1);
+        let (pkg, checksum_url) = &packages[0];
+        assert_eq!(pkg.version, "21.0.1");
+        assert!(pkg.download_url.ends_with("sapmachine-jdk-21.0.1_linux-x64_bin.tar.gz"));
+        assert!(checksum_url.as_deref().unwrap().ends_with(".sha256.txt"));
+    }
+}
@@ -1,5 +1,8 @@
 use async_trait::async_trait;
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::OnceLock;
 use crate::error::{JdkError, Result};
 
 
@@ -22,25 +25,128 @@ pub trait JdkSource: Send + Sync {
         Err(JdkError::JdkNotFound(major_version.to_string()))
     }
 
+    /// every release of a major version with the given image type, newest first.
+    /// sources that only publish the latest build return just that one
+    async fn list_releases(&self, major_version: u32, image: ImageType) -> Result<Vec<JdkPackage>> {
+        Ok(vec![self.find_image(major_version, image).await?])
+    }
+
+    /// find package by major version and image type, sources without a choice only have jdk
+    async fn find_image(&self, major_version: u32, image: ImageType) -> Result<JdkPackage> {
+        if image == ImageType::Jdk {
+            return self.find_package(major_version).await;
+        }
+        Err(JdkError::PackageNotFound(format!("{} ({} image)", major_version, image)))
+    }
+
+    /// find package by major version and bundle type, sources with a single bundle only have standard
+    async fn find_bundle(&self, major_version: u32, bundle: BundleType) -> Result<JdkPackage> {
+        let pkg = self.find_package(major_version).await?;
+        if pkg.bundle_type == bundle {
+            return Ok(pkg);
+        }
+        Err(JdkError::PackageNotFound(format!("{} ({} bundle)", major_version, bundle)))
+    }
+
 }
 
-pub fn detect_os() -> String {
-    #[cfg(target_os = "windows")]
-    return "windows".to_string();
+/// Platforms sources are asked for, in the names Adoptium uses
+const SUPPORTED_OS: [&str; 3] = ["linux", "mac", "windows"];
+const SUPPORTED_ARCH: [&str; 6] = ["x64", "aarch64", "ppc64le", "s390x", "riscv64", "arm"];
+
+/// `--os`/`--arch` of this run, set once before any source is queried
+static TARGET: OnceLock<(String, String)> = OnceLock::new();
 
-    #[cfg(target_os = "linux")]
-    return "linux".to_string();
+/// Download for another platform than this one, unknown platforms are an error
+pub fn set_target(os: Option<&str>, arch: Option<&str>) -> Result<()> {
+    let os = match os {
+        Some(os) => normalize_os(os).ok_or_else(|| {
+            JdkError::UnsupportedPlatform(format!("os '{}', expected one of {}", os, SUPPORTED_OS.join(", ")))
+        })?,
+        None => normalize_os(&host_os()).ok_or_else(|| {
+            JdkError::UnsupportedPlatform(format!("host os '{}', pass --os", host_os()))
+        })?,
+    };
+    let arch = match arch {
+        Some(arch) => normalize_arch(arch).ok_or_else(|| {
+            JdkError::UnsupportedPlatform(format!("arch '{}', expected one of {}", arch, SUPPORTED_ARCH.join(", ")))
+        })?,
+        None => normalize_arch(&host_arch()).ok_or_else(|| {
+            JdkError::UnsupportedPlatform(format!("host arch '{}', pass --arch", host_arch()))
+        })?,
+    };
+    let _ = TARGET.set((os.to_string(), arch.to_string()));
+    Ok(())
+}
 
-    #[cfg(target_os = "macos")]
-    return "mac".to_string();
+/// os packages are fetched for: `--os`, or this machine's
+pub fn detect_os() -> String {
+    TARGET.get().map(|(os, _)| os.clone()).unwrap_or_else(host_os)
 }
 
+/// arch packages are fetched for: `--arch`, or this machine's
 pub fn detect_arch() -> String {
-    #[cfg(target_arch = "x86_64")]
-    return "x64".to_string();
+    TARGET.get().map(|(_, arch)| arch.clone()).unwrap_or_else(host_arch)
+}
+
+/// true when packages are fetched for another platform than this one
+pub fn is_cross_target() -> bool {
+    detect_os() != host_os() || detect_arch() != host_arch()
+}
+
+/// os of this machine
+pub fn host_os() -> String {
+    match std::env::consts::OS {
+        "macos" => "mac".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// arch of this machine, in the names sources use when known
+pub fn host_arch() -> String {
+    match std::env::consts::ARCH {
+        "powerpc64" if cfg!(target_endian = "little") => "ppc64le".to_string(),
+        other => normalize_arch(other).unwrap_or(other).to_string(),
+    }
+}
+
+/// accepted os names (eg: "macos", "darwin" -> "mac")
+pub fn normalize_os(os: &str) -> Option<&'static str> {
+    match os.to_lowercase().as_str() {
+        "linux" => Some("linux"),
+        "mac" | "macos" | "darwin" | "osx" => Some("mac"),
+        "windows" | "win" => Some("windows"),
+        _ => None,
+    }
+}
+
+/// accepted arch names (eg: "amd64" -> "x64", "arm64" -> "aarch64", "armv7" -> "arm")
+pub fn normalize_arch(arch: &str) -> Option<&'static str> {
+    match arch.to_lowercase().as_str() {
+        "x64" | "x86_64" | "amd64" => Some("x64"),
+        "aarch64" | "arm64" => Some("aarch64"),
+        "ppc64le" | "ppc64el" => Some("ppc64le"),
+        "s390x" => Some("s390x"),
+        "riscv64" => Some("riscv64"),
+        "arm" | "arm32" | "armv7" | "armv7l" | "aarch32" => Some("arm"),
+        _ => None,
+    }
+}
+
+/// os name in vendor APIs and archive names, which call macOS "macos" where jsh and Adoptium say "mac"
+pub fn os_name(os: &str) -> &str {
+    match os {
+        "mac" => "macos",
+        other => other,
+    }
+}
 
-    #[cfg(target_arch = "aarch64")]
-    return "aarch64".to_string();
+/// Upper bound on metadata requests a source sends at once
+pub const MAX_CONCURRENT_REQUESTS: usize = 6;
+
+/// LTS releases: 8, 11, then every fourth release from 17
+pub fn is_lts_version(major: u32) -> bool {
+    major == 8 || major == 11 || (major >= 17 && (major - 17).is_multiple_of(4))
 }
 
 pub fn get_file_type(os: &str) -> &'static str {
@@ -52,6 +158,74 @@ pub fn get_file_type(os: &str) -> &'static str {
 }
 
 
+/// Package flavour: standard JDK, full with JavaFX, or lite without optional modules
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum BundleType {
+    #[default]
+    Standard,
+    Full,
+    Lite,
+}
+
+impl fmt::Display for BundleType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleType::Standard => write!(f, "standard"),
+            BundleType::Full => write!(f, "full"),
+            BundleType::Lite => write!(f, "lite"),
+        }
+    }
+}
+
+/// Hash algorithm of a published checksum
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksumType {
+    #[default]
+    Sha256,
+    /// BellSoft only publishes SHA-1 sums
+    Sha1,
+}
+
+impl fmt::Display for ChecksumType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChecksumType::Sha256 => write!(f, "SHA-256"),
+            ChecksumType::Sha1 => write!(f, "SHA-1"),
+        }
+    }
+}
+
+/// What an archive contains: a full JDK, a runtime only, debug symbols or the source code
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageType {
+    #[default]
+    Jdk,
+    Jre,
+    Debugimage,
+    Sources,
+}
+
+impl ImageType {
+    /// debug images and sources are not Java homes, they go next to the matching JDK
+    pub fn is_runtime(self) -> bool {
+        matches!(self, ImageType::Jdk | ImageType::Jre)
+    }
+}
+
+impl fmt::Display for ImageType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageType::Jdk => write!(f, "jdk"),
+            ImageType::Jre => write!(f, "jre"),
+            ImageType::Debugimage => write!(f, "debugimage"),
+            ImageType::Sources => write!(f, "sources"),
+        }
+    }
+}
+
 /// JDK package info
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JdkPackage {
@@ -67,7 +241,7 @@ pub struct JdkPackage {
     /// os（"windows", "linux", "mac"）
     pub os: String,
 
-    /// arch（"x64", "aarch64"）
+    /// arch（"x64", "aarch64", "ppc64le", "s390x", "riscv64", "arm"）
     pub arch: String,
 
     pub download_url: String,
@@ -80,6 +254,40 @@ pub struct JdkPackage {
 
     pub is_lts: bool,
 
-    /// SHA256 check sum
+    /// published check sum, SHA-256 unless `checksum_type` says otherwise
     pub checksum: Option<String>,
+
+    #[serde(default)]
+    pub checksum_type: ChecksumType,
+
+    /// standard, full (JavaFX) or lite
+    #[serde(default)]
+    pub bundle_type: BundleType,
+
+    /// jdk, jre, debugimage or sources
+    #[serde(default)]
+    pub image_type: ImageType,
+
+    /// JVM implementation（"hotspot", "openj9", "graal"）
+    pub jvm_impl: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_platform() {
+        assert_eq!(normalize_os("macOS"), Some("mac"));
+        assert_eq!(normalize_os("darwin"), Some("mac"));
+        assert_eq!(normalize_os("freebsd"), None);
+
+        assert_eq!(normalize_arch("amd64"), Some("x64"));
+        assert_eq!(normalize_arch("arm64"), Some("aarch64"));
+        assert_eq!(normalize_arch("ppc64el"), Some("ppc64le"));
+        assert_eq!(normalize_arch("armv7l"), Some("arm"));
+        assert_eq!(normalize_arch("s390x"), Some("s390x"));
+        assert_eq!(normalize_arch("mips"), None);
+        assert!(SUPPORTED_ARCH.iter().all(|arch| normalize_arch(arch) == Some(*arch)));
+    }
 }
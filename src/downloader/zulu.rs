@@ -0,0 +1,194 @@
+use async_trait::async_trait;
+use crate::downloader::cache;
+use crate::downloader::traits::{BundleType, ChecksumType, ImageType, JdkPackage, JdkSource, detect_arch, detect_os, get_file_type, is_lts_version, os_name};
+use crate::error::JdkError;
+use crate::error::Result;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+const API_URL: &str = "https://api.azul.com/metadata/v1/zulu/packages/";
+
+pub struct ZuluSource {
+    client: Client,
+}
+
+impl ZuluSource {
+    pub fn new() -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// query GA jdk packages for this platform, optionally limited to one major version
+    async fn fetch_packages(&self, major_version: Option<u32>) -> Result<Vec<JdkPackage>> {
+        let os = detect_os();
+        let archive_type = get_file_type(&os);
+        let mut query = vec![
+            ("os", os_name(&os).to_string()),
+            ("arch", zulu_arch(&detect_arch()).to_string()),
+            ("archive_type", archive_type.to_string()),
+            ("java_package_type", "jdk".to_string()),
+            ("javafx_bundled", "false".to_string()),
+            ("release_status", "ga".to_string()),
+            ("availability_types", "CA".to_string()),
+            ("latest", "true".to_string()),
+            ("include_fields", "sha256_hash,size,support_term".to_string()),
+            ("page_size", "1000".to_string()),
+        ];
+        if let Some(major) = major_version {
+            query.push(("java_version", major.to_string()));
+        }
+
+        let response: Vec<ZuluPackage> = cache::get_json(self.client.get(API_URL).query(&query)).await?;
+
+        // keep the newest plain package per major version
+        let mut latest: HashMap<u32, ZuluPackage> = HashMap::new();
+        for pkg in response.into_iter().filter(|p| p.is_plain()) {
+            let Some(&major) = pkg.java_version.first() else {
+                continue;
+            };
+            let newer = latest
+                .get(&major)
+                .is_none_or(|cur| (&pkg.java_version, &pkg.distro_version) > (&cur.java_version, &cur.distro_version));
+            if newer {
+                latest.insert(major, pkg);
+            }
+        }
+
+        let mut packages: Vec<JdkPackage> = latest
+            .into_values()
+            .map(|pkg| pkg.into_package(&os, archive_type))
+            .collect();
+        packages.sort_by_key(|p| std::cmp::Reverse(p.major_version));
+        Ok(packages)
+    }
+}
+
+/// Azul names 32-bit ARM hard float "aarch32hf" and little endian POWER "ppc64"
+fn zulu_arch(arch: &str) -> &str {
+    match arch {
+        "arm" => "aarch32hf",
+        "ppc64le" => "ppc64",
+        other => other,
+    }
+}
+
+// ============azul metadata api structs============
+#[derive(Deserialize, Debug)]
+struct ZuluPackage {
+    name: String,
+    download_url: String,
+    java_version: Vec<u32>,
+    #[serde(default)]
+    openjdk_build_number: Option<u32>,
+    #[serde(default)]
+    distro_version: Vec<u32>,
+    #[serde(default)]
+    sha256_hash: Option<String>,
+    #[serde(default)]
+    size: Option<u64>,
+    #[serde(default)]
+    support_term: Option<String>,
+}
+
+impl ZuluPackage {
+    /// skip musl, CRaC and other special builds that share the query
+    fn is_plain(&self) -> bool {
+        !["musl", "crac", "fx"].iter().any(|tag| self.name.contains(tag))
+    }
+
+    fn into_package(self, os: &str, archive_type: &str) -> JdkPackage {
+        let major_version = self.java_version.first().copied().unwrap_or_default();
+        let mut version = self
+            .java_version
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+        if let Some(build) = self.openjdk_build_number {
+            version = format!("{}+{}", version, build);
+        }
+        let is_lts = match self.support_term.as_deref() {
+            Some(term) => term == "lts",
+            None => is_lts_version(major_version),
+        };
+
+        JdkPackage {
+            version,
+            major_version,
+            vendor: "zulu".to_string(),
+            os: os.to_string(),
+            arch: detect_arch(),
+            download_url: self.download_url,
+            size: self.size.unwrap_or_default(),
+            file_type: archive_type.to_string(),
+            is_lts,
+            checksum: self.sha256_hash.filter(|h| !h.is_empty()),
+            checksum_type: ChecksumType::Sha256,
+            bundle_type: BundleType::Standard,
+            image_type: ImageType::Jdk,
+            jvm_impl: "hotspot".to_string(),
+        }
+    }
+}
+// ============azul metadata api structs============
+
+#[async_trait]
+impl JdkSource for ZuluSource {
+    fn name(&self) -> &str {
+        "Azul Zulu"
+    }
+
+    async fn fetch_version(&self) -> Result<Vec<JdkPackage>> {
+        self.fetch_packages(None).await
+    }
+
+    async fn find_package(&self, major_version: u32) -> Result<JdkPackage> {
+        self.fetch_packages(Some(major_version))
+            .await?
+            .into_iter()
+            .find(|p| p.major_version == major_version)
+            .ok_or_else(|| JdkError::PackageNotFound(major_version.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_package() {
+        let json = r#"[{
+            "package_uuid": "5ad1e2e1-4a0e-4bd4-9d1c-7f5f1a4c9f0a",
+            "name": "zulu17.46.19-ca-jdk17.0.9-linux_x64.tar.gz",
+            "java_version": [17, 0, 9],
+            "openjdk_build_number": 8,
+            "latest": true,
+            "download_url": "https://cdn.azul.com/zulu/bin/zulu17.46.19-ca-jdk17.0.9-linux_x64.tar.gz",
+            "product": "zulu",
+            "distro_version": [17, 46, 19, 0],
+            "availability_type": "CA",
+            "sha256_hash": "f9a4f5e6c1b5e0d4c7d3b0a4e2f3c6d8a1b2c3d4e5f60718293a4b5c6d7e8f90",
+            "size": 193275394,
+            "support_term": "lts"
+        }, {
+            "name": "zulu21.30.15-ca-jdk21.0.1-linux_musl_x64.tar.gz",
+            "java_version": [21, 0, 1],
+            "download_url": "https://cdn.azul.com/zulu/bin/zulu21.30.15-ca-jdk21.0.1-linux_musl_x64.tar.gz"
+        }]"#;
+        let packages: Vec<ZuluPackage> = serde_json::from_str(json).unwrap();
+        assert!(!packages[1].is_plain());
+
+        let pkg = packages.into_iter().next().unwrap().into_package("linux", "tar.gz");
+        assert_eq!(pkg.version, "17.0.9+8");
+        assert_eq!(pkg.major_version, 17);
+        assert_eq!(pkg.vendor, "zulu");
+        assert_eq!(pkg.size, 193275394);
+        assert!(pkg.is_lts);
+        assert!(pkg.checksum.is_some());
+    }
+}
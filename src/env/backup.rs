@@ -0,0 +1,218 @@
+use crate::error::{JdkError, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Number of backup sets kept before the oldest are pruned
+const KEEP_BACKUPS: usize = 10;
+const MANIFEST: &str = "manifest.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupEntry {
+    target: PathBuf,
+    /// file name inside the backup set, None if the target did not exist
+    backup: Option<String>,
+}
+
+/// Copies of every file touched by one change, restorable as a unit
+pub struct BackupSet {
+    dir: PathBuf,
+    entries: Vec<BackupEntry>,
+}
+
+impl BackupSet {
+    /// Start a backup set in a timestamped directory under `base`
+    pub fn begin(base: &Path) -> Result<Self> {
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let mut dir = base.join(stamp.to_string());
+        let mut n = 2;
+        while dir.exists() {
+            dir = base.join(format!("{}-{}", stamp, n));
+            n += 1;
+        }
+        Ok(Self { dir, entries: Vec::new() })
+    }
+
+    /// Copy `target` into the set before it gets modified
+    pub fn save(&mut self, target: &Path) -> Result<()> {
+        if self.entries.iter().any(|e| e.target == target) {
+            return Ok(());
+        }
+        fs::create_dir_all(&self.dir)?;
+        let backup = if target.exists() {
+            let name = format!(
+                "{}-{}",
+                self.entries.len(),
+                target.file_name().and_then(|n| n.to_str()).unwrap_or("file")
+            );
+            fs::copy(target, self.dir.join(&name))?;
+            Some(name)
+        } else {
+            None
+        };
+        self.entries.push(BackupEntry { target: target.to_path_buf(), backup });
+        Ok(())
+    }
+
+    /// Write the manifest and prune old sets
+    pub fn commit(self) -> Result<()> {
+        if self.entries.is_empty() {
+            return Ok(());
+        }
+        let manifest = serde_json::to_string_pretty(&self.entries)?;
+        fs::write(self.dir.join(MANIFEST), manifest)?;
+
+        if let Some(base) = self.dir.parent() {
+            let sets = list_sets(base)?;
+            for old in sets.iter().take(sets.len().saturating_sub(KEEP_BACKUPS)) {
+                fs::remove_dir_all(old).ok();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Restore the newest backup set under `base`, returning the restored files
+pub fn restore_latest(base: &Path) -> Result<Option<Vec<PathBuf>>> {
+    let Some(dir) = list_sets(base)?.pop() else {
+        return Ok(None);
+    };
+    let content = fs::read_to_string(dir.join(MANIFEST))?;
+    let entries: Vec<BackupEntry> = serde_json::from_str(&content)?;
+
+    let mut restored = Vec::new();
+    for entry in entries {
+        match &entry.backup {
+            Some(name) => {
+                let content = fs::read(dir.join(name))?;
+                write_atomic(&entry.target, &content)?;
+            }
+            None => {
+                // the change created this file
+                if entry.target.exists() {
+                    fs::remove_file(&entry.target)?;
+                }
+            }
+        }
+        restored.push(entry.target);
+    }
+    fs::remove_dir_all(&dir)?;
+    Ok(Some(restored))
+}
+
+/// Complete backup sets, oldest first
+fn list_sets(base: &Path) -> Result<Vec<PathBuf>> {
+    if !base.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut sets: Vec<PathBuf> = fs::read_dir(base)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.join(MANIFEST).is_file())
+        .collect();
+    sets.sort_by_key(|p| set_order(p));
+    Ok(sets)
+}
+
+/// Creation order of a set named "<stamp>" or "<stamp>-<n>", so "<stamp>-10" sorts after "<stamp>-2"
+fn set_order(set: &Path) -> (u64, u32) {
+    let name = set.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let (stamp, n) = name.split_once('-').unwrap_or((name, "1"));
+    (stamp.parse().unwrap_or(0), n.parse().unwrap_or(1))
+}
+
+/// Replace a file via temp file and rename so a crash never leaves it half written.
+/// Symlinks are followed so dotfile managers keep their links.
+pub fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+    let target = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let dir = target
+        .parent()
+        .ok_or_else(|| JdkError::EnvError(format!("Invalid path: {}", target.display())))?;
+    fs::create_dir_all(dir)?;
+
+    let name = target.file_name().and_then(|n| n.to_str()).unwrap_or("file");
+    let tmp = dir.join(format!(".{}.jsh-tmp-{}", name, std::process::id()));
+    let result = (|| {
+        let mut file = fs::File::create(&tmp)?;
+        std::io::Write::write_all(&mut file, content)?;
+        file.sync_all()?;
+        if let Ok(meta) = fs::metadata(&target) {
+            fs::set_permissions(&tmp, meta.permissions())?;
+        }
+        fs::rename(&tmp, &target)
+    })();
+    if result.is_err() {
+        fs::remove_file(&tmp).ok();
+    }
+    result.map_err(JdkError::IoError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("jsh-backup-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_backup_and_restore() {
+        let dir = test_dir("restore");
+        let backups = dir.join("backups");
+        let rc = dir.join(".bashrc");
+        let env = dir.join("env.sh");
+        fs::write(&rc, "alias ll='ls -l'\n").unwrap();
+
+        let mut set = BackupSet::begin(&backups).unwrap();
+        set.save(&rc).unwrap();
+        set.save(&env).unwrap();
+        write_atomic(&rc, b"alias ll='ls -l'\n. env.sh\n").unwrap();
+        write_atomic(&env, b"export JAVA_HOME=/jdk\n").unwrap();
+        set.commit().unwrap();
+
+        let restored = restore_latest(&backups).unwrap().unwrap();
+        assert_eq!(restored, vec![rc.clone(), env.clone()]);
+        assert_eq!(fs::read_to_string(&rc).unwrap(), "alias ll='ls -l'\n");
+        assert!(!env.exists());
+        assert!(restore_latest(&backups).unwrap().is_none());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_sets_created_in_the_same_second() {
+        let dir = test_dir("order");
+        for name in ["1700000000-10", "1700000000-2", "1700000000", "1699999999-3"] {
+            fs::create_dir_all(dir.join(name)).unwrap();
+            fs::write(dir.join(name).join(MANIFEST), "[]").unwrap();
+        }
+        let sets = list_sets(&dir).unwrap();
+        let names: Vec<_> = sets.iter().map(|p| p.file_name().unwrap().to_str().unwrap()).collect();
+        assert_eq!(names, vec!["1699999999-3", "1700000000", "1700000000-2", "1700000000-10"]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomic_follows_symlinks() {
+        let dir = test_dir("symlink");
+        let real = dir.join("dotfiles-bashrc");
+        let link = dir.join(".bashrc");
+        fs::write(&real, "old\n").unwrap();
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        write_atomic(&link, b"new\n").unwrap();
+        assert!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_to_string(&real).unwrap(), "new\n");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
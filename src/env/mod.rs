@@ -4,13 +4,16 @@ pub mod windows;
 #[cfg(not(target_os = "windows"))]
 pub mod unix;
 
+#[cfg(not(target_os = "windows"))]
+pub mod backup;
+
+pub mod shell;
+
 use crate::error::Result;
 use std::path::Path;
-use winreg::RegKey;
 
 pub trait EnvUpdater {
     fn update_java_home(&self, path: &Path) -> Result<()>;
-    fn update_path_with_key(&self, env_key: &RegKey, java_home: &Path) -> Result<()>;
 }
 
 #[cfg(target_os = "windows")]
@@ -0,0 +1,229 @@
+use clap::ValueEnum;
+use std::path::{Path, PathBuf};
+
+/// Environment variable set by the `jsh init` snippet so jsh knows it runs inside the shell function
+pub const SHELL_INTEGRATION_VAR: &str = "JSH_SHELL_INTEGRATION";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    #[value(name = "nu", alias = "nushell")]
+    Nushell,
+    #[value(name = "pwsh", alias = "powershell")]
+    Pwsh,
+}
+
+impl Shell {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Fish => "fish",
+            Shell::Nushell => "nu",
+            Shell::Pwsh => "pwsh",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = Path::new(name).file_name()?.to_str()?;
+        match name {
+            n if n.contains("zsh") => Some(Shell::Zsh),
+            n if n.contains("fish") => Some(Shell::Fish),
+            n if n == "nu" || n.contains("nushell") => Some(Shell::Nushell),
+            n if n.contains("pwsh") || n.contains("powershell") => Some(Shell::Pwsh),
+            n if n.contains("bash") || n == "sh" => Some(Shell::Bash),
+            _ => None,
+        }
+    }
+
+    /// Shell integration first, then $SHELL, defaulting to bash
+    pub fn detect() -> Self {
+        [SHELL_INTEGRATION_VAR, "SHELL"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find_map(|value| Self::from_name(&value))
+            .unwrap_or(Shell::Bash)
+    }
+
+    /// Quote a value as a literal string for this shell
+    pub fn quote(&self, value: &str) -> String {
+        match self {
+            Shell::Bash | Shell::Zsh => format!("'{}'", value.replace('\'', r"'\''")),
+            Shell::Fish => format!("'{}'", value.replace('\\', r"\\").replace('\'', r"\'")),
+            Shell::Nushell => format!("\"{}\"", value.replace('\\', r"\\").replace('"', "\\\"")),
+            Shell::Pwsh => format!("'{}'", value.replace('\'', "''")),
+        }
+    }
+
+    /// Code that sets JAVA_HOME and PATH when evaluated by this shell
+    pub fn render_env(&self, java_home: &Path, path: &[PathBuf]) -> String {
+        let java_home = self.quote(&java_home.to_string_lossy());
+        let joined_path = || {
+            std::env::join_paths(path)
+                .map(|p| self.quote(&p.to_string_lossy()))
+                .unwrap_or_default()
+        };
+        match self {
+            Shell::Bash | Shell::Zsh => {
+                format!("export JAVA_HOME={}\nexport PATH={}\n", java_home, joined_path())
+            }
+            Shell::Fish => {
+                let path: Vec<String> = path
+                    .iter()
+                    .map(|p| self.quote(&p.to_string_lossy()))
+                    .collect();
+                format!("set -gx JAVA_HOME {}\nset -gx PATH {}\n", java_home, path.join(" "))
+            }
+            Shell::Nushell => {
+                // nuon record for `load-env`, nushell cannot eval code
+                let path: Vec<String> = path
+                    .iter()
+                    .map(|p| self.quote(&p.to_string_lossy()))
+                    .collect();
+                format!("{{JAVA_HOME: {}, PATH: [{}]}}\n", java_home, path.join(", "))
+            }
+            Shell::Pwsh => {
+                format!("$env:JAVA_HOME = {}\n$env:PATH = {}\n", java_home, joined_path())
+            }
+        }
+    }
+
+    /// Snippet for `eval "$(jsh init <shell>)"` that wraps jsh in a shell function
+    pub fn init_script(&self) -> String {
+        let name = self.name();
+        match self {
+            Shell::Bash | Shell::Zsh => format!(
+                r#"export {var}={name}
+jsh() {{
+  command jsh "$@" || return $?
+  case "$1" in
+    use|local|remove|uninstall) eval "$(command jsh env --shell {name})" ;;
+  esac
+}}
+eval "$(command jsh env --shell {name} 2>/dev/null)"
+"#,
+                var = SHELL_INTEGRATION_VAR,
+            ),
+            Shell::Fish => format!(
+                r#"set -gx {var} {name}
+function jsh
+    command jsh $argv; or return $status
+    switch "$argv[1]"
+        case use local remove uninstall
+            command jsh env --shell {name} | source
+    end
+end
+command jsh env --shell {name} 2>/dev/null | source
+"#,
+                var = SHELL_INTEGRATION_VAR,
+            ),
+            Shell::Nushell => format!(
+                r#"$env.{var} = "{name}"
+def --env --wrapped jsh [...args] {{
+    ^jsh ...$args
+    if ($args | length) > 0 and ($args.0 in [use local remove uninstall]) {{
+        ^jsh env --shell {name} | from nuon | load-env
+    }}
+}}
+try {{ ^jsh env --shell {name} | from nuon | load-env }}
+"#,
+                var = SHELL_INTEGRATION_VAR,
+            ),
+            Shell::Pwsh => format!(
+                r#"$env:{var} = '{name}'
+$global:JshExe = (Get-Command jsh -CommandType Application | Select-Object -First 1).Source
+function jsh {{
+    & $global:JshExe @args
+    if ($LASTEXITCODE -ne 0) {{ return }}
+    if ($args.Count -gt 0 -and @('use', 'local', 'remove', 'uninstall') -contains $args[0]) {{
+        & $global:JshExe env --shell {name} | Out-String | Invoke-Expression
+    }}
+}}
+& $global:JshExe env --shell {name} 2>$null | Out-String | Invoke-Expression
+"#,
+                var = SHELL_INTEGRATION_VAR,
+            ),
+        }
+    }
+
+    /// Line users add to their rc file to load the snippet
+    pub fn init_hint(&self) -> &'static str {
+        match self {
+            Shell::Bash => "eval \"$(jsh init bash)\"  # in ~/.bashrc",
+            Shell::Zsh => "eval \"$(jsh init zsh)\"  # in ~/.zshrc",
+            Shell::Fish => "jsh init fish | source  # in ~/.config/fish/config.fish",
+            Shell::Nushell => "jsh init nu | save -f ~/.jsh.nu; source ~/.jsh.nu  # in config.nu",
+            Shell::Pwsh => "jsh init pwsh | Out-String | Invoke-Expression  # in $PROFILE",
+        }
+    }
+}
+
+/// PATH with the bin dirs of other JDKs removed and `java_home/bin` in front
+pub fn build_path(java_home: &Path, current: &str, stale_bins: &[PathBuf]) -> Vec<PathBuf> {
+    let java_bin = java_home.join("bin");
+    let mut path = vec![java_bin.clone()];
+    path.extend(
+        std::env::split_paths(current)
+            .filter(|p| !p.as_os_str().is_empty())
+            .filter(|p| *p != java_bin && !stale_bins.contains(p)),
+    );
+    path
+}
+
+#[cfg(all(test, not(target_os = "windows")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_path() {
+        let stale = vec![PathBuf::from("/jdks/jdk-11/bin")];
+        let path = build_path(
+            Path::new("/jdks/jdk-17"),
+            "/jdks/jdk-11/bin:/usr/bin:/jdks/jdk-17/bin:/bin",
+            &stale,
+        );
+        assert_eq!(
+            path,
+            vec![
+                PathBuf::from("/jdks/jdk-17/bin"),
+                PathBuf::from("/usr/bin"),
+                PathBuf::from("/bin"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_env() {
+        let home = Path::new("/opt/it's jdk");
+        let path = vec![home.join("bin"), PathBuf::from("/usr/bin")];
+
+        assert_eq!(
+            Shell::Bash.render_env(home, &path),
+            "export JAVA_HOME='/opt/it'\\''s jdk'\nexport PATH='/opt/it'\\''s jdk/bin:/usr/bin'\n"
+        );
+        assert_eq!(
+            Shell::Fish.render_env(home, &path),
+            "set -gx JAVA_HOME '/opt/it\\'s jdk'\nset -gx PATH '/opt/it\\'s jdk/bin' '/usr/bin'\n"
+        );
+        assert_eq!(
+            Shell::Nushell.render_env(home, &path),
+            "{JAVA_HOME: \"/opt/it's jdk\", PATH: [\"/opt/it's jdk/bin\", \"/usr/bin\"]}\n"
+        );
+        assert_eq!(
+            Shell::Pwsh.render_env(home, &path),
+            "$env:JAVA_HOME = '/opt/it''s jdk'\n$env:PATH = '/opt/it''s jdk/bin:/usr/bin'\n"
+        );
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Shell::from_name("/usr/bin/zsh"), Some(Shell::Zsh));
+        assert_eq!(Shell::from_name("/usr/local/bin/fish"), Some(Shell::Fish));
+        assert_eq!(Shell::from_name("bash"), Some(Shell::Bash));
+        assert_eq!(Shell::from_name("/usr/bin/nu"), Some(Shell::Nushell));
+        assert_eq!(Shell::from_name("/opt/microsoft/powershell/7/pwsh"), Some(Shell::Pwsh));
+        assert_eq!(Shell::from_name("/bin/tcsh"), None);
+    }
+}
@@ -1,93 +1,226 @@
+use crate::config::Config;
 use crate::env::EnvUpdater;
+use crate::env::backup::{self, BackupSet, write_atomic};
+use crate::env::shell::Shell;
 use crate::error::{JdkError, Result};
-use std::fs::{OpenOptions};
-use std::io::{BufRead, BufReader, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-pub struct UnixEnvUpdater;
+/// Trailing comment on the line that sources the env file
+const SOURCE_MARKER: &str = "# jsh env";
+/// Trailing comment on lines older versions wrote into the startup file
+const LEGACY_MARKER: &str = "# jsh managed";
+
+pub struct UnixEnvUpdater {
+    shell: Shell,
+}
 
 impl UnixEnvUpdater {
     pub fn new() -> Self {
-        Self
+        Self::with_shell(Shell::detect())
+    }
+
+    pub fn with_shell(shell: Shell) -> Self {
+        Self { shell }
     }
 
-    fn get_shell_rc_path() -> Result<std::path::PathBuf> {
+    /// Startup file jsh writes to for the given shell
+    pub fn shell_config_path(shell: Shell) -> Result<PathBuf> {
         let home = dirs::home_dir()
             .ok_or_else(|| JdkError::EnvError("Cannot find home directory".to_string()))?;
+        // fish and pwsh follow XDG on every unix, nushell uses the platform config dir
+        let xdg_config_home = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from);
+        let xdg_config = xdg_config_home.clone().unwrap_or_else(|| home.join(".config"));
+
+        Ok(match shell {
+            Shell::Bash => home.join(".bashrc"),
+            Shell::Zsh => std::env::var_os("ZDOTDIR")
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from)
+                .unwrap_or(home)
+                .join(".zshrc"),
+            Shell::Fish => xdg_config.join("fish").join("conf.d").join("jsh.fish"),
+            Shell::Nushell => xdg_config_home
+                .or_else(dirs::config_dir)
+                .unwrap_or(xdg_config)
+                .join("nushell")
+                .join("env.nu"),
+            Shell::Pwsh => xdg_config
+                .join("powershell")
+                .join("Microsoft.PowerShell_profile.ps1"),
+        })
+    }
+
+    /// Env file jsh owns for the given shell, sourced from the startup file
+    pub fn env_file_path(shell: Shell) -> Result<PathBuf> {
+        let name = match shell {
+            Shell::Bash | Shell::Zsh => "env.sh",
+            Shell::Fish => "env.fish",
+            Shell::Nushell => "env.nu",
+            Shell::Pwsh => "env.ps1",
+        };
+        Ok(Config::config_dir()?.join(name))
+    }
 
-        // Try to detect shell
-        if let Ok(shell) = std::env::var("SHELL") {
-            if shell.contains("zsh") {
-                return Ok(home.join(".zshrc"));
-            } else if shell.contains("bash") {
-                return Ok(home.join(".bashrc"));
-            }
+    fn backups_dir() -> Result<PathBuf> {
+        Ok(Config::config_dir()?.join("backups"))
+    }
+
+    /// Command that loads the env file in a running shell
+    pub fn reload_hint(shell: Shell, path: &Path) -> String {
+        match shell {
+            Shell::Bash | Shell::Zsh | Shell::Fish => format!("source {}", path.display()),
+            Shell::Nushell => format!("source {}", shell.quote(&path.to_string_lossy())),
+            Shell::Pwsh => format!(". {}", shell.quote(&path.to_string_lossy())),
         }
+    }
 
-        // Default to .bashrc
-        Ok(home.join(".bashrc"))
+    /// Env file content that sets JAVA_HOME and prepends its bin dir to PATH
+    fn render_env_file(shell: Shell, java_home: &Path) -> String {
+        let java_home = shell.quote(&java_home.to_string_lossy());
+        let lines = match shell {
+            Shell::Bash | Shell::Zsh => vec![
+                format!("export JAVA_HOME={}", java_home),
+                "export PATH=\"$JAVA_HOME/bin:$PATH\"".to_string(),
+            ],
+            Shell::Fish => vec![
+                format!("set -gx JAVA_HOME {}", java_home),
+                "set -gx PATH $JAVA_HOME/bin $PATH".to_string(),
+            ],
+            Shell::Nushell => vec![
+                format!("$env.JAVA_HOME = {}", java_home),
+                "$env.PATH = ($env.PATH | split row (char esep) | prepend ($env.JAVA_HOME | path join bin))".to_string(),
+            ],
+            Shell::Pwsh => vec![
+                format!("$env:JAVA_HOME = {}", java_home),
+                "$env:PATH = (Join-Path $env:JAVA_HOME 'bin') + [IO.Path]::PathSeparator + $env:PATH".to_string(),
+            ],
+        };
+        format!("# Generated by jsh, changes are overwritten by `jsh use`\n{}\n", lines.join("\n"))
     }
 
-    fn update_shell_rc(&self, java_home: &Path) -> Result<()> {
-        let rc_path = Self::get_shell_rc_path()?;
-        let java_home_str = java_home.to_string_lossy();
-
-        // Read existing content
-        let mut lines = Vec::new();
-        let mut found_java_home = false;
-        let mut found_path = false;
-
-        if rc_path.exists() {
-            let file = std::fs::File::open(&rc_path)
-                .map_err(|e| JdkError::IoError(e))?;
-            let reader = BufReader::new(file);
-
-            for line in reader.lines() {
-                let line = line.map_err(|e| JdkError::IoError(e))?;
-                if line.contains("export JAVA_HOME=") && line.contains("# jsh managed") {
-                    lines.push(format!("export JAVA_HOME=\"{}\"  # jsh managed", java_home_str));
-                    found_java_home = true;
-                } else if line.contains("export PATH=") && line.contains("$JAVA_HOME/bin") && line.contains("# jsh managed") {
-                    lines.push(format!("export PATH=\"$JAVA_HOME/bin:$PATH\"  # jsh managed"));
-                    found_path = true;
-                } else {
-                    lines.push(line);
-                }
-            }
+    /// Guarded line that loads the env file from the startup file
+    fn render_source_line(shell: Shell, env_file: &Path) -> String {
+        let env_file = shell.quote(&env_file.to_string_lossy());
+        let line = match shell {
+            Shell::Bash | Shell::Zsh => format!("[ -f {0} ] && . {0}", env_file),
+            Shell::Fish => format!("test -f {0}; and source {0}", env_file),
+            // nushell resolves `source` at parse time, the file is created before this line
+            Shell::Nushell => format!("source {}", env_file),
+            Shell::Pwsh => format!("if (Test-Path {0}) {{ . {0} }}", env_file),
+        };
+        format!("{}  {}", line, SOURCE_MARKER)
+    }
+
+    /// Startup file content with the source line added once and lines
+    /// from older jsh versions removed. None if nothing changes.
+    fn merge_source_line(existing: &str, source_line: &str) -> Option<String> {
+        let has_legacy = existing.lines().any(|l| l.contains(LEGACY_MARKER));
+        if existing.lines().any(|l| l.contains(SOURCE_MARKER)) && !has_legacy {
+            return None;
         }
 
-        // Add new entries if not found
-        if !found_java_home {
-            lines.push(format!("\n# jsh managed - do not edit manually"));
-            lines.push(format!("export JAVA_HOME=\"{}\"  # jsh managed", java_home_str));
+        let mut lines: Vec<&str> = existing
+            .lines()
+            .filter(|l| !l.contains(LEGACY_MARKER) && !l.contains(SOURCE_MARKER))
+            .collect();
+        while lines.last().is_some_and(|l| l.trim().is_empty()) {
+            lines.pop();
         }
-        if !found_path {
-            lines.push(format!("export PATH=\"$JAVA_HOME/bin:$PATH\"  # jsh managed"));
+        if !lines.is_empty() {
+            lines.push("");
         }
+        lines.push(source_line);
+        Some(format!("{}\n", lines.join("\n")))
+    }
 
-        // Write back
-        let mut file = OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .create(true)
-            .open(&rc_path)
-            .map_err(|e| JdkError::IoError(e))?;
-
-        for line in lines {
-            writeln!(file, "{}", line).map_err(|e| JdkError::IoError(e))?;
+    fn update_env_file(&self, java_home: &Path) -> Result<()> {
+        let env_file = Self::env_file_path(self.shell)?;
+        let rc_path = Self::shell_config_path(self.shell)?;
+        let mut backup = BackupSet::begin(&Self::backups_dir()?)?;
+
+        backup.save(&env_file)?;
+        write_atomic(&env_file, Self::render_env_file(self.shell, java_home).as_bytes())?;
+        println!("[OK] Updated {}", env_file.display());
+
+        let existing = if rc_path.exists() {
+            std::fs::read_to_string(&rc_path).map_err(JdkError::IoError)?
+        } else {
+            String::new()
+        };
+        let source_line = Self::render_source_line(self.shell, &env_file);
+        if let Some(content) = Self::merge_source_line(&existing, &source_line) {
+            backup.save(&rc_path)?;
+            write_atomic(&rc_path, content.as_bytes())?;
+            println!("[OK] Added jsh env to {}", rc_path.display());
         }
 
-        println!("[OK] Updated {}", rc_path.display());
-        println!("  Please run: source {}", rc_path.display());
+        backup.commit()
+    }
 
-        Ok(())
+    /// Roll back the last change made to the env file and startup file
+    pub fn restore() -> Result<Option<Vec<PathBuf>>> {
+        backup::restore_latest(&Self::backups_dir()?)
     }
 }
 
 impl EnvUpdater for UnixEnvUpdater {
     fn update_java_home(&self, path: &Path) -> Result<()> {
-        self.update_shell_rc(path)?;
+        self.update_env_file(path)?;
         Ok(())
     }
 
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_env_file() {
+        let home = Path::new("/jdks/jdk-17");
+        let bash = UnixEnvUpdater::render_env_file(Shell::Bash, home);
+        assert!(bash.starts_with("# Generated by jsh"));
+        assert!(bash.contains("\nexport JAVA_HOME='/jdks/jdk-17'\n"));
+        assert!(UnixEnvUpdater::render_env_file(Shell::Fish, home)
+            .contains("\nset -gx JAVA_HOME '/jdks/jdk-17'\n"));
+        assert!(UnixEnvUpdater::render_env_file(Shell::Nushell, home)
+            .contains("\n$env.JAVA_HOME = \"/jdks/jdk-17\"\n"));
+        assert!(UnixEnvUpdater::render_env_file(Shell::Pwsh, home)
+            .contains("\n$env:JAVA_HOME = '/jdks/jdk-17'\n"));
+    }
+
+    #[test]
+    fn test_render_source_line() {
+        let env = Path::new("/home/me/.config/jsh/env.sh");
+        assert_eq!(
+            UnixEnvUpdater::render_source_line(Shell::Bash, env),
+            "[ -f '/home/me/.config/jsh/env.sh' ] && . '/home/me/.config/jsh/env.sh'  # jsh env"
+        );
+        assert_eq!(
+            UnixEnvUpdater::render_source_line(Shell::Pwsh, Path::new("/c/env.ps1")),
+            "if (Test-Path '/c/env.ps1') { . '/c/env.ps1' }  # jsh env"
+        );
+    }
+
+    #[test]
+    fn test_merge_source_line() {
+        let line = UnixEnvUpdater::render_source_line(Shell::Bash, Path::new("/jsh/env.sh"));
+
+        let fresh = UnixEnvUpdater::merge_source_line("alias ll='ls -l'\n", &line).unwrap();
+        assert_eq!(fresh, format!("alias ll='ls -l'\n\n{}\n", line));
+
+        // added only once
+        assert_eq!(UnixEnvUpdater::merge_source_line(&fresh, &line), None);
+
+        // lines written by older versions are dropped
+        let legacy = "alias ll='ls -l'\n\
+            # jsh managed - do not edit manually\n\
+            export JAVA_HOME='/jdks/jdk-17'  # jsh managed\n\
+            export PATH=\"$JAVA_HOME/bin:$PATH\"  # jsh managed\n\
+            export EDITOR=vim\n";
+        let merged = UnixEnvUpdater::merge_source_line(legacy, &line).unwrap();
+        assert_eq!(merged, format!("alias ll='ls -l'\nexport EDITOR=vim\n\n{}\n", line));
+    }
+}
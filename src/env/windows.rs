@@ -17,6 +17,43 @@ impl WindowsEnvUpdater {
             .map_err(|e| JdkError::EnvError(format!("Failed to open registry key (need administrator permission): {}", e)))
     }
 
+    fn update_path_with_key(&self, env_key: &RegKey, java_home: &Path) -> Result<()> {
+        let path_value: String = env_key
+            .get_value("Path")
+            .unwrap_or_else(|_| String::new());
+
+        let java_bin = java_home.join("bin").to_string_lossy().to_string();
+        
+        // Remove old Java paths
+        let mut paths: Vec<String> = path_value
+            .split(';')
+            .filter(|p| !p.is_empty())
+            .map(|s| s.to_string())
+            .collect();
+
+        // Remove existing Java bin paths (more precise matching)
+        let java_home_str = java_home.to_string_lossy().to_lowercase();
+        paths.retain(|p| {
+            let p_lower = p.to_lowercase();
+            // Only remove paths that are actually within a JDK installation
+            !(p_lower.contains(&java_home_str) || 
+              (p_lower.contains("\\bin") && (p_lower.contains("\\jdk") || p_lower.contains("\\jre"))))
+        });
+
+        // Add new Java bin path at the beginning
+        paths.insert(0, java_bin.clone());
+
+        let new_path = paths.join(";");
+        
+        env_key
+            .set_value("Path", &new_path)
+            .map_err(|e| JdkError::EnvError(format!("Failed to update PATH: {}", e)))?;
+
+        println!("[OK] Updated PATH to include: {}", java_bin);
+
+        Ok(())
+    }
+
     fn broadcast_environment_change() -> Result<()> {
         use windows::Win32::Foundation::*;
         use windows::Win32::UI::WindowsAndMessaging::*;
@@ -63,41 +100,4 @@ impl EnvUpdater for WindowsEnvUpdater {
 
         Ok(())
     }
-    
-    fn update_path_with_key(&self, env_key: &RegKey, java_home: &Path) -> Result<()> {
-        let path_value: String = env_key
-            .get_value("Path")
-            .unwrap_or_else(|_| String::new());
-
-        let java_bin = java_home.join("bin").to_string_lossy().to_string();
-        
-        // Remove old Java paths
-        let mut paths: Vec<String> = path_value
-            .split(';')
-            .filter(|p| !p.is_empty())
-            .map(|s| s.to_string())
-            .collect();
-
-        // Remove existing Java bin paths (more precise matching)
-        let java_home_str = java_home.to_string_lossy().to_lowercase();
-        paths.retain(|p| {
-            let p_lower = p.to_lowercase();
-            // Only remove paths that are actually within a JDK installation
-            !(p_lower.contains(&java_home_str) || 
-              (p_lower.contains("\\bin") && (p_lower.contains("\\jdk") || p_lower.contains("\\jre"))))
-        });
-
-        // Add new Java bin path at the beginning
-        paths.insert(0, java_bin.clone());
-
-        let new_path = paths.join(";");
-        
-        env_key
-            .set_value("Path", &new_path)
-            .map_err(|e| JdkError::EnvError(format!("Failed to update PATH: {}", e)))?;
-
-        println!("[OK] Updated PATH to include: {}", java_bin);
-
-        Ok(())
-    }
 }
@@ -20,6 +20,9 @@ pub enum JdkError {
     #[error("Network error: {0}")]
     NetworkError(String),
 
+    #[error("Network error: {url} returned HTTP {status}")]
+    HttpStatus { url: String, status: u16 },
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
@@ -29,17 +32,38 @@ pub enum JdkError {
     #[error("JSON error: {0}")]
     JsonError(#[from] serde_json::Error),
 
+    #[error("JDK {0} is currently active, switch to another JDK first or use --force")]
+    JdkInUse(String),
+
+    #[error("Version {version} matches several JDKs: {candidates}")]
+    AmbiguousVersion { version: String, candidates: String },
+
+    #[error("JDK {version} requested by {origin} is not installed, run 'jsh list' or 'jsh download'")]
+    UnresolvedVersion { version: String, origin: String },
+
+    #[error("Active JDK {actual} does not satisfy {spec}")]
+    UnsatisfiedVersion { spec: String, actual: String },
+
     #[error("No JDK is currently active")]
     NoActiveJdk,
 
+    #[error("Checksum mismatch: expected {expected}, got {actual}")]
+    ChecksumMismatch { expected: String, actual: String },
+
     #[error("Extraction failed: {0}")]
     ExtractionError(String),
 
     #[error("Package not found for version: {0}")]
     PackageNotFound(String),
 
+    #[error("Unknown vendor '{vendor}', supported vendors: {supported}")]
+    UnknownVendor { vendor: String, supported: String },
+
     #[error("Invalid version format: {0}")]
     InvalidVersion(String),
+
+    #[error("Unsupported platform: {0}")]
+    UnsupportedPlatform(String),
 }
 
 pub type Result<T> = std::result::Result<T, JdkError>;
@@ -1,5 +1,6 @@
 use crate::config::JdkInfo;
 use crate::error::Result;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use walkdir::WalkDir;
@@ -23,10 +24,10 @@ impl JdkDetector {
         // Check JAVA_HOME
         if let Ok(java_home) = std::env::var("JAVA_HOME") {
             let path = PathBuf::from(java_home);
-            if Self::is_valid_jdk(&path) {
-                if let Some(info) = Self::get_jdk_info(&path) {
-                    jdks.push(info);
-                }
+            if Self::is_valid_runtime(&path)
+                && let Some(info) = Self::get_jdk_info(&path)
+            {
+                jdks.push(info);
             }
         }
         
@@ -67,7 +68,7 @@ impl JdkDetector {
     }
     
     /// Scan a directory for JDK installations
-    fn scan_directory(path: &Path) -> Result<Vec<JdkInfo>> {
+    pub fn scan_directory(path: &Path) -> Result<Vec<JdkInfo>> {
         let mut jdks = Vec::new();
 
         if !path.exists() {
@@ -81,18 +82,24 @@ impl JdkDetector {
             .filter_map(|e| e.ok())
         {
             let path = entry.path();
-            if Self::is_valid_jdk(path) {
-                if let Some(info) = Self::get_jdk_info(path) {
-                    jdks.push(info);
-                }
+            if Self::is_valid_runtime(path)
+                && !Self::is_bundled_jre(path)
+                && let Some(info) = Self::get_jdk_info(path)
+            {
+                jdks.push(info);
             }
         }
         
         Ok(jdks)
     }
 
-    /// Check if a directory is a valid JDK installation
+    /// Check if a directory is a valid JDK installation, a JRE is not one
     pub fn is_valid_jdk(path: &Path) -> bool {
+        Self::is_valid_runtime(path) && crate::jdk::shims::tool_path(path, "javac").exists()
+    }
+
+    /// Check if a directory is a Java home, either a JDK or a JRE
+    pub fn is_valid_runtime(path: &Path) -> bool {
         if !path.is_dir() {
             return false;
         }
@@ -108,6 +115,12 @@ impl JdkDetector {
         lib_dir.exists()
     }
 
+    /// The `jre` dir inside a JDK 8, it belongs to the JDK around it
+    fn is_bundled_jre(path: &Path) -> bool {
+        path.file_name().is_some_and(|name| name == "jre")
+            && path.parent().is_some_and(Self::is_valid_jdk)
+    }
+
     /// Get the java executable path for a JDK
     fn get_java_executable(jdk_path: &Path) -> PathBuf {
         #[cfg(target_os = "windows")]
@@ -135,15 +148,51 @@ impl JdkDetector {
             .ok()?;
 
         let stderr = String::from_utf8_lossy(&output.stderr);
-        let (version, vendor, java_version) = Self::parse_version_output(&stderr);
+        let (version, mut vendor, java_version) = Self::parse_version_output(&stderr);
+
+        let release = Self::read_release_file(path);
+        let arch = release.get("OS_ARCH").map(|a| Self::normalize_arch(a));
+
+        // some builds (eg: Liberica) only name themselves in the release file
+        if matches!(vendor.as_deref(), None | Some("OpenJDK"))
+            && let Some(name) = release.get("IMPLEMENTOR").and_then(|i| Self::parse_vendor(i))
+        {
+            vendor = Some(name.to_string());
+        }
+
+        let native_image = crate::jdk::shims::tool_path(path, "native-image").exists()
+            || path.join("bin").join("native-image.cmd").exists();
+        let jvm_impl = Self::parse_jvm_impl(&stderr, &release);
+        let image_type = if crate::jdk::shims::tool_path(path, "javac").exists() { "jdk" } else { "jre" };
 
         Some(JdkInfo {
             path: path.to_path_buf(),
             version,
             vendor,
             java_version,
+            arch,
+            native_image,
+            jvm_impl,
+            image_type: Some(image_type.to_string()),
         })
     }
+
+    /// Read KEY="value" pairs from the JDK `release` file
+    pub fn read_release_file(path: &Path) -> HashMap<String, String> {
+        let content = std::fs::read_to_string(path.join("release")).unwrap_or_default();
+        content
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().trim_matches('"').to_string()))
+            .collect()
+    }
+
+    /// Map release file arch names onto the names used by download sources
+    fn normalize_arch(arch: &str) -> String {
+        crate::downloader::traits::normalize_arch(arch)
+            .unwrap_or(arch)
+            .to_string()
+    }
     
     /// Parse java -version output
     fn parse_version_output(output: &str) -> (String, Option<String>, Option<String>) {
@@ -153,44 +202,81 @@ impl JdkDetector {
 
         for line in output.lines() {
             // Parse version line: java version "1.8.0_291" or openjdk version "17.0.2"
-            if line.contains("version") {
-                if let Some(start) = line.find('"') {
-                    if let Some(end) = line[start + 1..].find('"') {
-                        let full_version = &line[start + 1..start + 1 + end];
-                        java_version = Some(full_version.to_string());
-
-                        // Extract major version
-                        if full_version.starts_with("1.") {
-                            // Old format: 1.8.0_291 -> 8
-                            if let Some(major) = full_version.split('.').nth(1) {
-                                version = major.to_string();
-                            }
-                        } else {
-                            // New format: 17.0.2 -> 17
-                            if let Some(major) = full_version.split('.').next() {
-                                version = major.to_string();
-                            }
-                        }
+            if line.contains("version")
+                && let Some(start) = line.find('"')
+                && let Some(end) = line[start + 1..].find('"')
+            {
+                let full_version = &line[start + 1..start + 1 + end];
+                java_version = Some(full_version.to_string());
+
+                // Extract major version
+                if full_version.starts_with("1.") {
+                    // Old format: 1.8.0_291 -> 8
+                    if let Some(major) = full_version.split('.').nth(1) {
+                        version = major.to_string();
+                    }
+                } else {
+                    // New format: 17.0.2 -> 17
+                    if let Some(major) = full_version.split('.').next() {
+                        version = major.to_string();
                     }
                 }
             }
             
-            // Parse vendor information
-            if line.contains("OpenJDK") {
+            // Parse vendor information, the first distribution named wins over the generic
+            // OpenJDK label (OpenJ9 VM lines also mention Eclipse)
+            if let Some(name) = Self::parse_vendor(line)
+                && matches!(vendor.as_deref(), None | Some("OpenJDK"))
+            {
+                vendor = Some(name.to_string());
+            } else if line.contains("OpenJDK") && vendor.is_none() {
                 vendor = Some("OpenJDK".to_string());
-            } else if line.contains("Oracle") {
-                vendor = Some("Oracle".to_string());
-            } else if line.contains("Temurin") || line.contains("Eclipse") {
-                vendor = Some("Eclipse Temurin".to_string());
-            } else if line.contains("Zulu") {
-                vendor = Some("Azul Zulu".to_string());
-            } else if line.contains("Microsoft") {
-                vendor = Some("Microsoft".to_string());
             }
         }
         
         (version, vendor, java_version)
     }
+
+    /// JVM implementation from java -version output, falling back to JVM_VARIANT in the release file
+    fn parse_jvm_impl(output: &str, release: &HashMap<String, String>) -> Option<String> {
+        if output.contains("OpenJ9") {
+            Some("openj9".to_string())
+        } else if output.contains("GraalVM") || output.contains("jvmci") {
+            Some("graal".to_string())
+        } else if let Some(variant) = release.get("JVM_VARIANT") {
+            Some(variant.to_lowercase())
+        } else if output.contains("64-Bit Server VM") || output.contains("HotSpot") {
+            Some("hotspot".to_string())
+        } else {
+            None
+        }
+    }
+
+    /// Distribution named in a java -version line
+    fn parse_vendor(line: &str) -> Option<&'static str> {
+        if line.contains("GraalVM") {
+            // Oracle GraalVM and GraalVM Community
+            Some(if line.contains("Oracle") { "Oracle GraalVM" } else { "GraalVM" })
+        } else if line.contains("Semeru") || line.contains("IBM") {
+            Some("IBM Semeru")
+        } else if line.contains("Corretto") || line.contains("Amazon") {
+            Some("Amazon Corretto")
+        } else if line.contains("Temurin") || line.contains("Eclipse") || line.contains("Adoptium") {
+            Some("Eclipse Temurin")
+        } else if line.contains("Zulu") || line.contains("Azul") {
+            Some("Azul Zulu")
+        } else if line.contains("Microsoft") {
+            Some("Microsoft")
+        } else if line.contains("SapMachine") || line.contains("SAP SE") {
+            Some("SAP SapMachine")
+        } else if line.contains("Liberica") || line.contains("BellSoft") {
+            Some("BellSoft Liberica")
+        } else if line.contains("Oracle") {
+            Some("Oracle")
+        } else {
+            None
+        }
+    }
 }
 
 #[cfg(test)]
@@ -210,7 +296,80 @@ Java HotSpot(TM) 64-Bit Server VM (build 25.291-b10, mixed mode)"#;
 OpenJDK Runtime Environment Temurin-17.0.2+8 (build 17.0.2+8)
 OpenJDK 64-Bit Server VM Temurin-17.0.2+8 (build 17.0.2+8, mixed mode)"#;
         
-        let (version, _, _) = JdkDetector::parse_version_output(output2);
+        let (version, vendor, _) = JdkDetector::parse_version_output(output2);
         assert_eq!(version, "17");
+        assert_eq!(vendor.as_deref(), Some("Eclipse Temurin"));
+
+        let output3 = r#"openjdk version "17.0.9" 2023-10-17 LTS
+OpenJDK Runtime Environment Corretto-17.0.9.8.1 (build 17.0.9+8-LTS)
+OpenJDK 64-Bit Server VM Corretto-17.0.9.8.1 (build 17.0.9+8-LTS, mixed mode, sharing)"#;
+
+        let (_, vendor, java_version) = JdkDetector::parse_version_output(output3);
+        assert_eq!(vendor.as_deref(), Some("Amazon Corretto"));
+        assert_eq!(java_version.as_deref(), Some("17.0.9"));
+
+        let output4 = r#"openjdk version "21.0.1" 2023-10-17
+OpenJDK Runtime Environment (build 21.0.1+12-29)
+OpenJDK 64-Bit Server VM (build 21.0.1+12-29, mixed mode, sharing)"#;
+
+        let (_, vendor, _) = JdkDetector::parse_version_output(output4);
+        assert_eq!(vendor.as_deref(), Some("OpenJDK"));
+
+        let output5 = r#"openjdk version "21.0.1" 2023-10-17
+OpenJDK Runtime Environment GraalVM CE 21.0.1+12.1 (build 21.0.1+12-jvmci-23.1-b19)
+OpenJDK 64-Bit Server VM GraalVM CE 21.0.1+12.1 (build 21.0.1+12-jvmci-23.1-b19, mixed mode, sharing)"#;
+
+        let (_, vendor, _) = JdkDetector::parse_version_output(output5);
+        assert_eq!(vendor.as_deref(), Some("GraalVM"));
+
+        let output6 = r#"openjdk version "17.0.9" 2023-10-17 LTS
+OpenJDK Runtime Environment Microsoft-8035246 (build 17.0.9+8-LTS)
+OpenJDK 64-Bit Server VM Microsoft-8035246 (build 17.0.9+8-LTS, mixed mode, sharing)"#;
+
+        let (_, vendor, _) = JdkDetector::parse_version_output(output6);
+        assert_eq!(vendor.as_deref(), Some("Microsoft"));
+
+        let output7 = r#"openjdk version "17.0.9" 2023-10-17 LTS
+OpenJDK Runtime Environment SapMachine (build 17.0.9+9-LTS-sapmachine)
+OpenJDK 64-Bit Server VM SapMachine (build 17.0.9+9-LTS-sapmachine, mixed mode, sharing)"#;
+
+        let (_, vendor, _) = JdkDetector::parse_version_output(output7);
+        assert_eq!(vendor.as_deref(), Some("SAP SapMachine"));
+        assert_eq!(JdkDetector::parse_vendor("BellSoft"), Some("BellSoft Liberica"));
+
+        let output8 = r#"openjdk version "17.0.9" 2023-10-17
+IBM Semeru Runtime Open Edition 17.0.9.0 (build 17.0.9+9)
+Eclipse OpenJ9 VM 17.0.9.0 (build openj9-0.41.0, JRE 17 Linux amd64-64-Bit Compressed References 20231017_568 (JIT enabled, AOT enabled)"#;
+
+        let (version, vendor, _) = JdkDetector::parse_version_output(output8);
+        assert_eq!(version, "17");
+        assert_eq!(vendor.as_deref(), Some("IBM Semeru"));
+        assert_eq!(JdkDetector::parse_jvm_impl(output8, &HashMap::new()).as_deref(), Some("openj9"));
+        assert_eq!(JdkDetector::parse_jvm_impl(output2, &HashMap::new()).as_deref(), Some("hotspot"));
+    }
+
+    #[test]
+    fn test_jre_is_not_a_jdk() {
+        let base = std::env::temp_dir().join(format!("jsh-detect-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&base);
+        let jdk = base.join("jdk8");
+        let jre = jdk.join("jre");
+        for home in [&jdk, &jre] {
+            std::fs::create_dir_all(home.join("lib")).unwrap();
+            std::fs::create_dir_all(home.join("bin")).unwrap();
+            std::fs::write(JdkDetector::get_java_executable(home), "").unwrap();
+        }
+        std::fs::write(crate::jdk::shims::tool_path(&jdk, "javac"), "").unwrap();
+
+        assert!(JdkDetector::is_valid_jdk(&jdk));
+        assert!(!JdkDetector::is_valid_jdk(&jre));
+        assert!(JdkDetector::is_valid_runtime(&jre));
+        // the jre inside a JDK 8 is part of it, not a runtime of its own
+        assert!(JdkDetector::is_bundled_jre(&jre));
+
+        std::fs::remove_file(crate::jdk::shims::tool_path(&jdk, "javac")).unwrap();
+        assert!(!JdkDetector::is_bundled_jre(&jre));
+
+        std::fs::remove_dir_all(&base).ok();
     }
 }
@@ -1,6 +1,8 @@
 use crate::config::{Config, JdkInfo}; // [注释] 引入Config配置类和JdkInfo信息结构体
 use crate::error::{JdkError, Result}; // [注释] 引入自定义错误类型和Result别名
+use crate::downloader::traits::{JdkPackage, host_arch, normalize_arch}; // [注释] 引入下载包信息、本机架构检测和架构名称归一化函数
 use crate::jdk::detector::JdkDetector; // [注释] 引入JdkDetector类，用于检测和扫描系统JDK
+use crate::jdk::spec::JdkSpec; // [注释] 引入版本描述解析器，用于匹配用户输入的版本
 
 pub struct JdkManager { // [注释] 定义公共结构体JdkManager，JDK管理器，封装JDK的核心操作
     config: Config, // [注释] 私有字段config，存储JDK配置信息
@@ -12,6 +14,11 @@ impl JdkManager { // [注释] 为JdkManager结构体实现方法
         Ok(Self { config }) // [注释] 构造JdkManager实例并返回，使用字段初始化简写
     }
 
+    #[cfg(test)]
+    pub(crate) fn with_config(config: Config) -> Self { // [注释] 测试用构造函数，不读取磁盘上的配置
+        Self { config }
+    }
+
     pub fn config(&self) -> &Config { // [注释] 公共方法，返回配置对象的不可变引用
         &self.config // [注释] 返回config字段的引用，不转移所有权
     }
@@ -26,32 +33,117 @@ impl JdkManager { // [注释] 为JdkManager结构体实现方法
         
         // Update config with newly found JDKs
         for jdk in &detected { // [注释] 遍历检测到的每个JDK信息
-            let key = jdk.version.clone(); // [注释] 克隆版本号作为HashMap的键
-            if !self.config.jdks.contains_key(&key) { // [注释] 检查该版本是否已经在配置中存在
-                self.config.add_jdk(key, jdk.clone()); // [注释] 如果是新发现的JDK，添加到配置中
+            if self.config.ignored.contains(&jdk.path) { // [注释] 跳过用户已移除的外部JDK
+                continue;
             }
+            self.register(jdk.clone()); // [注释] 按路径去重后注册，同一主版本的多个JDK可以共存
         }
         
         self.config.save()?; // [注释] 将更新后的配置保存到磁盘
         Ok(detected) // [注释] 返回检测到的JDK列表
     }
 
+    /// Register a JDK and return its key, already registered paths keep their key
+    pub fn register(&mut self, info: JdkInfo) -> String { // [注释] 公共方法，注册单个JDK并返回其注册表键
+        if let Some(key) = self.config.find_key_by_path(&info.path) { // [注释] 按路径查找是否已注册
+            self.config.add_jdk(key.clone(), info); // [注释] 刷新厂商、native-image等检测信息
+            return key; // [注释] 已注册则保持原有键不变，保证键的稳定性
+        }
+        self.config.ignored.retain(|p| p != &info.path); // [注释] 显式注册时取消忽略
+        let key = self.config.unique_key(&info); // [注释] 生成厂商+完整版本的复合键，冲突时追加架构或序号
+        self.config.add_jdk(key.clone(), info); // [注释] 添加到配置中
+        key // [注释] 返回新生成的键
+    }
+
     /// Get all registered JDKs
     pub fn list_jdks(&self) -> Vec<(&String, &JdkInfo)> { // [注释] 公共方法，获取所有已注册JDK的排序列表
-        let mut jdks: Vec<_> = self.config.jdks.iter().collect(); // [注释] 将HashMap的迭代器收集为Vec，包含(版本号, JdkInfo)元组的引用
+        let mut jdks: Vec<_> = self.config.jdks.iter().collect(); // [注释] 将HashMap的迭代器收集为Vec，包含(键, JdkInfo)元组的引用
         jdks.sort_by(|a, b| { // [注释] 使用自定义比较函数对JDK列表排序
-            // Try to parse as numbers for proper sorting
-            let a_num: std::result::Result<u32, _> = a.0.parse(); // [注释] 尝试将第一个版本号解析为无符号整数
-            let b_num: std::result::Result<u32, _> = b.0.parse(); // [注释] 尝试将第二个版本号解析为无符号整数
-            
-            match (a_num, b_num) { // [注释] 匹配解析结果的组合
-                (Ok(a), Ok(b)) => a.cmp(&b), // [注释] 如果两个都解析成功，按数值比较（如8 < 11 < 17）
-                _ => a.0.cmp(b.0), // [注释] 否则按字符串字典序比较
-            }
+            a.1.version_parts().cmp(&b.1.version_parts()) // [注释] 按完整版本号数值比较（如8 < 11.0.8 < 17.0.2 < 17.0.9）
+                .then_with(|| a.0.cmp(b.0)) // [注释] 版本相同时按键的字典序比较
         });
         jdks // [注释] 返回排序后的JDK列表
     }
 
+    /// Resolve an exact key or a version spec (eg: 17, 17.0.9, >=17, lts, temurin@21) to a registry key
+    pub fn resolve_key(&self, version: &str) -> Option<String> { // [注释] 公共方法，将用户输入解析为注册表键
+        if self.config.jdks.contains_key(version) { // [注释] 优先精确匹配注册表键（如temurin-17.0.9）
+            return Some(version.to_string()); // [注释] 精确匹配直接返回
+        }
+        let spec = JdkSpec::parse(version).ok()?; // [注释] 解析版本描述，无法解析则视为找不到
+
+        if let Some(current) = &self.config.current_jdk // [注释] 如果当前JDK满足版本描述
+            && !spec.version.prefers_newest() // [注释] lts和latest总是选择最新的匹配版本
+            && self.config.get_jdk(current).is_some_and(|info| spec.matches(info))
+        {
+            return Some(current.clone()); // [注释] 保持当前JDK不变，避免在同主版本之间跳动
+        }
+
+        let host_arch = host_arch(); // [注释] 获取本机架构，优先选择与本机架构一致的JDK
+        self.config.jdks.iter() // [注释] 遍历所有已注册JDK
+            .filter(|(_, info)| spec.matches(info)) // [注释] 只保留满足版本描述的JDK
+            .max_by(|a, b| { // [注释] 选出最合适的默认JDK
+                let a_native = a.1.arch.as_deref().is_none_or(|arch| arch == host_arch); // [注释] 未知架构视为本机架构
+                let b_native = b.1.arch.as_deref().is_none_or(|arch| arch == host_arch);
+                (a_native, a.1.version_parts(), !a.1.is_jre()).cmp(&(b_native, b.1.version_parts(), !b.1.is_jre())) // [注释] 先比较架构，再比较完整版本号，同版本时JDK优先于JRE
+                    .then_with(|| b.0.cmp(a.0)) // [注释] 完全相同时取字典序较小的键，保证结果稳定
+            })
+            .map(|(key, _)| key.clone()) // [注释] 返回选中的键
+    }
+
+    /// Resolve an exact key, or a version spec that matches exactly one JDK
+    pub fn resolve_unique_key(&self, version: &str) -> Result<String> { // [注释] 公共方法，用于删除等破坏性操作，不允许有歧义
+        if self.config.jdks.contains_key(version) { // [注释] 优先精确匹配注册表键
+            return Ok(version.to_string());
+        }
+        let spec = JdkSpec::parse(version)?; // [注释] 解析版本描述，语法错误直接报告
+        self.unique_match(version, |info| spec.matches(info)) // [注释] 要求恰好一个JDK满足版本描述
+    }
+
+    /// Resolve the installed JDK (or JRE when `jre`) of a downloadable release, on the same arch
+    pub fn resolve_release(&self, package: &JdkPackage, jre: bool) -> Result<String> { // [注释] 公共方法，查找与下载包同厂商、同版本、同架构的已安装JDK
+        let spec = JdkSpec::release(&package.vendor, &package.version)?; // [注释] 去掉厂商构建号，如17.0.9.8.1 -> 17.0.9
+        let arch = normalize_arch(&package.arch).unwrap_or(&package.arch); // [注释] 统一架构名称，如amd64 -> x64
+        self.unique_match(&format!("{} ({})", spec, arch), |info| { // [注释] 错误信息中带上架构
+            spec.matches(info)
+                && info.is_jre() == jre // [注释] JDK和JRE分开匹配
+                && info.arch.as_deref().is_none_or(|a| a == arch) // [注释] 未知架构视为匹配
+        })
+    }
+
+    /// The only registered key whose JDK passes `keep`
+    fn unique_match(&self, version: &str, keep: impl Fn(&JdkInfo) -> bool) -> Result<String> { // [注释] 私有方法，唯一匹配的公共逻辑
+        let mut matches: Vec<&String> = self.config.jdks.iter() // [注释] 收集满足条件的所有键
+            .filter(|(_, info)| keep(info))
+            .map(|(key, _)| key)
+            .collect();
+        matches.sort(); // [注释] 排序保证错误信息稳定
+        match matches.as_slice() { // [注释] 根据匹配数量决定结果
+            [] => Err(JdkError::JdkNotFound(version.to_string())), // [注释] 没有匹配
+            [key] => Ok((*key).clone()), // [注释] 唯一匹配
+            keys => Err(JdkError::AmbiguousVersion { // [注释] 多个匹配，要求用户给出完整的键
+                version: version.to_string(),
+                candidates: keys.iter().map(|k| k.as_str()).collect::<Vec<_>>().join(", "),
+            }),
+        }
+    }
+
+    /// Check if a JDK was installed by jsh into the jdks directory
+    pub fn is_managed(&self, info: &JdkInfo) -> bool { // [注释] 公共方法，判断JDK是否由jsh下载安装
+        Config::jdks_dir() // [注释] 获取jsh的JDK安装目录
+            .map(|dir| info.path.starts_with(dir)) // [注释] 路径位于安装目录下即为jsh管理
+            .unwrap_or(false)
+    }
+
+    /// Remove a JDK from the registry, external JDKs are ignored by later scans
+    pub fn unregister(&mut self, key: &str) -> Option<JdkInfo> { // [注释] 公共方法，从注册表中移除JDK
+        let info = self.config.remove_jdk(key)?; // [注释] 移除条目，若为当前JDK则同时清空current_jdk
+        if !self.is_managed(&info) { // [注释] 外部JDK仍会被扫描到，需要记录到忽略列表
+            self.config.ignored.push(info.path.clone());
+        }
+        Some(info) // [注释] 返回被移除的JDK信息
+    }
+
     /// Get current active JDK
     pub fn get_current(&self) -> Option<&JdkInfo> { // [注释] 公共方法，获取当前激活的JDK信息
         self.config.get_current() // [注释] 委托给Config的get_current方法，返回Option<&JdkInfo>
@@ -63,22 +155,23 @@ impl JdkManager { // [注释] 为JdkManager结构体实现方法
     }
 
     /// Switch to a specific JDK version
-    pub fn switch_jdk(&mut self, version: &str) -> Result<&JdkInfo> { // [注释] 公共方法，切换到指定版本的JDK
-        let jdk = self.config.get_jdk(version) // [注释] 从配置中获取指定版本的JDK信息
+    pub fn switch_jdk(&mut self, version: &str) -> Result<(String, &JdkInfo)> { // [注释] 公共方法，切换到指定版本的JDK，返回解析后的键和JDK信息
+        let key = self.resolve_key(version) // [注释] 将键或主版本号解析为注册表键
             .ok_or_else(|| JdkError::JdkNotFound(version.to_string()))?; // [注释] 如果找不到，返回JdkNotFound错误
+        let jdk = self.config.get_jdk(&key).unwrap(); // [注释] unwrap安全因为resolve_key只返回已存在的键
         
         // Verify JDK still exists
-        if !JdkDetector::is_valid_jdk(&jdk.path) { // [注释] 验证JDK路径是否仍然有效（文件可能已被删除）
+        if !JdkDetector::is_valid_runtime(&jdk.path) { // [注释] 验证JDK路径是否仍然有效（文件可能已被删除），JRE也可以切换
             return Err(JdkError::InvalidPath(format!( // [注释] 如果路径无效，返回InvalidPath错误
                 "JDK path no longer valid: {}",
                 jdk.path.display() // [注释] 显示无效的JDK路径
             )));
         }
         
-        self.config.set_current(version.to_string()); // [注释] 在配置中设置当前激活的JDK版本
+        self.config.set_current(key.clone()); // [注释] 在配置中设置当前激活的JDK键
         self.config.save()?; // [注释] 保存更新后的配置到磁盘
         
-        Ok(self.config.get_jdk(version).unwrap()) // [泣释] 返回切换后JDK的信息引用，unwrap安全因为前面已验证存在
+        Ok((key.clone(), self.config.get_jdk(&key).unwrap())) // [注释] 返回切换后JDK的键和信息引用
     }
 
     /// Save configuration
@@ -86,3 +179,91 @@ impl JdkManager { // [注释] 为JdkManager结构体实现方法
         self.config.save() // [注释] 委托给Config的save方法
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn jdk(path: &str, vendor: &str, java_version: &str) -> JdkInfo { // [注释] 构造测试用的JDK信息
+        JdkInfo {
+            path: PathBuf::from(path),
+            version: java_version.split('.').next().unwrap().to_string(),
+            vendor: Some(vendor.to_string()),
+            java_version: Some(java_version.to_string()),
+            arch: Some("x64".to_string()),
+            native_image: false,
+            jvm_impl: None,
+            image_type: None,
+        }
+    }
+
+    #[test]
+    fn test_resolve_unique_key() {
+        let mut manager = JdkManager::with_config(Config::default());
+        let temurin = manager.register(jdk("/opt/temurin-17", "Eclipse Temurin", "17.0.9"));
+        let zulu = manager.register(jdk("/opt/zulu-17", "Azul Zulu", "17.0.2"));
+        manager.register(jdk("/opt/temurin-21", "Eclipse Temurin", "21.0.1"));
+
+        assert_eq!(manager.resolve_unique_key(&zulu).unwrap(), zulu); // [注释] 精确键总是唯一
+        assert_eq!(manager.resolve_unique_key("17.0.9").unwrap(), temurin);
+        assert_eq!(manager.resolve_unique_key("zulu@17").unwrap(), zulu);
+        match manager.resolve_unique_key("17") { // [注释] 两个JDK 17都匹配时必须报错而不是任选其一
+            Err(JdkError::AmbiguousVersion { version, candidates }) => {
+                assert_eq!(version, "17");
+                assert_eq!(candidates, format!("{}, {}", temurin, zulu));
+            }
+            other => panic!("expected AmbiguousVersion, got {:?}", other.map_err(|e| e.to_string())),
+        }
+        assert!(matches!(manager.resolve_unique_key("11"), Err(JdkError::JdkNotFound(_))));
+    }
+
+    #[test]
+    fn test_resolve_release() {
+        let package = |vendor: &str, version: &str, arch: &str| { // [注释] 构造测试用的下载包信息
+            serde_json::from_value::<JdkPackage>(serde_json::json!({
+                "version": version, "major_version": 17, "vendor": vendor, "os": "linux", "arch": arch,
+                "download_url": "", "size": 0, "file_type": "tar.gz", "is_lts": true, "checksum": null,
+                "jvm_impl": "hotspot",
+            }))
+            .unwrap()
+        };
+        let mut manager = JdkManager::with_config(Config::default());
+        let x64 = manager.register(jdk("/opt/temurin-17", "Eclipse Temurin", "17.0.9"));
+        let mut arm = jdk("/opt/temurin-17-arm", "Eclipse Temurin", "17.0.9");
+        arm.arch = Some("aarch64".to_string());
+        let arm = manager.register(arm);
+        let corretto = manager.register(jdk("/opt/corretto-17", "Amazon Corretto", "17.0.9"));
+
+        assert_eq!(manager.resolve_release(&package("temurin", "17.0.9+9", "x64"), false).unwrap(), x64);
+        assert_eq!(manager.resolve_release(&package("temurin", "17.0.9+9", "arm64"), false).unwrap(), arm); // [注释] 架构名称先归一化
+        assert_eq!(manager.resolve_release(&package("corretto", "17.0.9.8.1", "x64"), false).unwrap(), corretto);
+        assert!(matches!( // [注释] 只有JDK时找不到对应的JRE
+            manager.resolve_release(&package("temurin", "17.0.9+9", "x64"), true),
+            Err(JdkError::JdkNotFound(_))
+        ));
+
+        let second = manager.register(jdk("/opt/jdks/temurin-17", "Eclipse Temurin", "17.0.9"));
+        match manager.resolve_release(&package("temurin", "17.0.9+9", "x64"), false) { // [注释] 同一版本注册了两次时列出候选项
+            Err(JdkError::AmbiguousVersion { candidates, .. }) => {
+                assert_eq!(candidates, format!("{}, {}", x64, second));
+            }
+            other => panic!("expected AmbiguousVersion, got {:?}", other.map_err(|e| e.to_string())),
+        }
+    }
+
+    #[test]
+    fn test_unregister_external_jdk() {
+        let mut manager = JdkManager::with_config(Config::default());
+        let key = manager.register(jdk("/usr/lib/jvm/java-17", "Eclipse Temurin", "17.0.9"));
+        manager.config_mut().current_jdk = Some(key.clone());
+
+        let info = manager.unregister(&key).unwrap();
+        assert!(manager.config().get_jdk(&key).is_none());
+        assert!(manager.get_current_version().is_none()); // [注释] 移除当前JDK时清空current_jdk
+        assert!(manager.config().ignored.contains(&info.path)); // [注释] 外部JDK加入忽略列表，避免下次扫描重新注册
+
+        manager.register(info.clone()); // [注释] 显式注册时取消忽略
+        assert!(!manager.config().ignored.contains(&info.path));
+    }
+}
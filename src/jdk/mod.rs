@@ -1,4 +1,7 @@
 pub mod detector;
 pub mod manager;
+pub mod resolver;
+pub mod shims;
+pub mod spec;
 
 pub use manager::JdkManager;
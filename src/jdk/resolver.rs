@@ -0,0 +1,178 @@
+use crate::config::JdkInfo;
+use crate::error::{JdkError, Result};
+use crate::jdk::spec::JdkSpec;
+use crate::jdk::JdkManager;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that overrides the JDK for the current shell
+pub const SHELL_OVERRIDE_VAR: &str = "JSH_JAVA_VERSION";
+
+/// Project files checked in every directory, in order of precedence
+pub const VERSION_FILES: [&str; 3] = [".java-version", ".sdkmanrc", ".tool-versions"];
+
+/// Where the active JDK selection came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Origin {
+    Shell,
+    ProjectFile(PathBuf),
+    Global,
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Origin::Shell => write!(f, "{} environment variable", SHELL_OVERRIDE_VAR),
+            Origin::ProjectFile(path) => write!(f, "{}", path.display()),
+            Origin::Global => write!(f, "global config"),
+        }
+    }
+}
+
+/// A version request found in a project file
+#[derive(Debug, Clone)]
+pub struct VersionFile {
+    pub path: PathBuf,
+    pub version: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Resolution {
+    pub key: String,
+    pub info: JdkInfo,
+    pub origin: Origin,
+}
+
+/// Walk up from `start` and return the first project file that requests a JDK
+pub fn find_version_file(start: &Path) -> Option<VersionFile> {
+    for dir in start.ancestors() {
+        for name in VERSION_FILES {
+            let path = dir.join(name);
+            if !path.is_file() {
+                continue;
+            }
+            if let Some(version) = parse_version_file(&path) {
+                return Some(VersionFile { path, version });
+            }
+        }
+    }
+    None
+}
+
+/// Read the requested version from a .java-version, .sdkmanrc or .tool-versions file
+pub fn parse_version_file(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    let name = path.file_name()?.to_str()?;
+    let mut lines = content
+        .lines()
+        .map(|l| l.split('#').next().unwrap_or("").trim())
+        .filter(|l| !l.is_empty());
+
+    match name {
+        ".sdkmanrc" => lines
+            .find_map(|l| l.strip_prefix("java="))
+            .map(|v| v.trim().to_string()),
+        ".tool-versions" => lines.find_map(|l| {
+            let mut fields = l.split_whitespace();
+            // asdf allows fallback versions after the first one
+            (fields.next() == Some("java")).then(|| fields.next()).flatten()
+        })
+        .map(|v| v.to_string()),
+        _ => lines.next().map(|v| v.to_string()),
+    }
+}
+
+/// Resolve the active JDK: shell override, then project file, then global config
+pub fn resolve(manager: &JdkManager, cwd: &Path) -> Result<Option<Resolution>> {
+    if let Ok(version) = std::env::var(SHELL_OVERRIDE_VAR)
+        && !version.trim().is_empty()
+    {
+        return resolve_version(manager, version.trim(), Origin::Shell).map(Some);
+    }
+
+    if let Some(file) = find_version_file(cwd) {
+        return resolve_version(manager, &file.version, Origin::ProjectFile(file.path)).map(Some);
+    }
+
+    Ok(manager.get_current_version().and_then(|key| {
+        manager.config().get_jdk(key).map(|info| Resolution {
+            key: key.clone(),
+            info: info.clone(),
+            origin: Origin::Global,
+        })
+    }))
+}
+
+fn resolve_version(manager: &JdkManager, version: &str, origin: Origin) -> Result<Resolution> {
+    let unresolved = || JdkError::UnresolvedVersion {
+        version: version.to_string(),
+        origin: origin.to_string(),
+    };
+    // validate the syntax so typos are reported as such
+    if manager.config().get_jdk(version).is_none() {
+        JdkSpec::parse(version)?;
+    }
+    let key = manager.resolve_key(version).ok_or_else(unresolved)?;
+    let info = manager.config().get_jdk(&key).ok_or_else(unresolved)?.clone();
+    Ok(Resolution { key, info, origin })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("jsh-project-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("service").join("src")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_parse_version_files() {
+        let dir = project_dir("parse");
+
+        fs::write(dir.join(".java-version"), "# pinned\n17.0.9\n").unwrap();
+        assert_eq!(parse_version_file(&dir.join(".java-version")).as_deref(), Some("17.0.9"));
+
+        fs::write(dir.join(".sdkmanrc"), "# sdkman\nmaven=3.9.5\njava=17.0.9-tem\n").unwrap();
+        assert_eq!(parse_version_file(&dir.join(".sdkmanrc")).as_deref(), Some("17.0.9-tem"));
+
+        fs::write(dir.join(".tool-versions"), "nodejs 20.9.0\njava temurin-17.0.9 zulu-11\n").unwrap();
+        assert_eq!(parse_version_file(&dir.join(".tool-versions")).as_deref(), Some("temurin-17.0.9"));
+
+        fs::write(dir.join(".tool-versions"), "java corretto-17.0.9.8.1\n").unwrap();
+        let version = parse_version_file(&dir.join(".tool-versions")).unwrap();
+        assert_eq!(version, "corretto-17.0.9.8.1");
+        assert_eq!(JdkSpec::parse(&version).unwrap().to_string(), "corretto@17.0.9");
+
+        fs::write(dir.join(".tool-versions"), "nodejs 20.9.0\n").unwrap();
+        assert_eq!(parse_version_file(&dir.join(".tool-versions")), None);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_find_version_file_walks_up() {
+        let dir = project_dir("walk");
+        let nested = dir.join("service").join("src");
+
+        fs::write(dir.join(".tool-versions"), "java 21\n").unwrap();
+        let found = find_version_file(&nested).unwrap();
+        assert_eq!(found.path, dir.join(".tool-versions"));
+        assert_eq!(found.version, "21");
+
+        // the nearest directory wins
+        fs::write(dir.join("service").join(".sdkmanrc"), "java=11.0.21-zulu\n").unwrap();
+        let found = find_version_file(&nested).unwrap();
+        assert_eq!(found.path, dir.join("service").join(".sdkmanrc"));
+
+        // .java-version wins within the same directory
+        fs::write(dir.join("service").join(".java-version"), "17\n").unwrap();
+        let found = find_version_file(&nested).unwrap();
+        assert_eq!(found.version, "17");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,151 @@
+use crate::config::Config;
+use crate::error::{JdkError, Result};
+use crate::jdk::JdkManager;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory holding one shim per JDK tool (java, javac, jar, ...)
+pub fn shims_dir() -> Result<PathBuf> {
+    Ok(Config::data_dir()?.join("shims"))
+}
+
+/// Check if the shims directory is on PATH
+pub fn is_on_path() -> bool {
+    let Ok(dir) = shims_dir() else {
+        return false;
+    };
+    std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).any(|p| p == dir))
+        .unwrap_or(false)
+}
+
+/// Recreate shims for every tool found in the bin dir of a registered JDK
+pub fn rehash(manager: &JdkManager) -> Result<Vec<String>> {
+    let dir = shims_dir()?;
+    fs::create_dir_all(&dir)?;
+    let jsh = std::env::current_exe()?;
+
+    let tools: BTreeSet<String> = manager
+        .list_jdks()
+        .iter()
+        .flat_map(|(_, info)| list_tools(&info.path.join("bin")))
+        .collect();
+
+    for tool in &tools {
+        write_shim(&dir, &jsh, tool)?;
+    }
+
+    // drop shims for tools no registered JDK provides anymore
+    for entry in fs::read_dir(&dir)?.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+        if !tools.contains(stem) {
+            fs::remove_file(&path)?;
+        }
+    }
+
+    Ok(tools.into_iter().collect())
+}
+
+/// Rehash only if the user has set up shims before
+pub fn refresh_if_enabled(manager: &JdkManager) -> Result<()> {
+    if shims_dir()?.is_dir() {
+        rehash(manager)?;
+    }
+    Ok(())
+}
+
+/// Path of a tool inside a JDK
+pub fn tool_path(jdk_path: &Path, tool: &str) -> PathBuf {
+    if cfg!(target_os = "windows") {
+        jdk_path.join("bin").join(format!("{}.exe", tool))
+    } else {
+        jdk_path.join("bin").join(tool)
+    }
+}
+
+/// Executable tool names in a JDK bin dir, without extension
+fn list_tools(bin_dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(bin_dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| is_executable(p))
+        .filter_map(|p| {
+            let name = if cfg!(target_os = "windows") {
+                p.file_stem()
+            } else {
+                p.file_name()
+            };
+            name.and_then(|n| n.to_str()).map(|n| n.to_string())
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path)
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(target_os = "windows")]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn write_shim(dir: &Path, jsh: &Path, tool: &str) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let jsh = jsh.to_string_lossy().replace('\'', r"'\''");
+    let path = dir.join(tool);
+    let content = format!("#!/bin/sh\n# jsh shim\nexec '{}' exec {} \"$@\"\n", jsh, tool);
+    fs::write(&path, content)?;
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+        .map_err(JdkError::IoError)
+}
+
+#[cfg(target_os = "windows")]
+fn write_shim(dir: &Path, jsh: &Path, tool: &str) -> Result<()> {
+    let path = dir.join(format!("{}.cmd", tool));
+    let content = format!("@echo off\r\n\"{}\" exec {} %*\r\n", jsh.display(), tool);
+    fs::write(&path, content).map_err(JdkError::IoError)
+}
+
+#[cfg(all(test, not(target_os = "windows")))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn test_list_tools_and_write_shim() {
+        let dir = std::env::temp_dir().join(format!("jsh-shims-{}", std::process::id()));
+        let bin = dir.join("jdk").join("bin");
+        fs::create_dir_all(&bin).unwrap();
+        for (name, mode) in [("java", 0o755), ("javac", 0o755), ("README", 0o644)] {
+            fs::write(bin.join(name), "").unwrap();
+            fs::set_permissions(bin.join(name), fs::Permissions::from_mode(mode)).unwrap();
+        }
+
+        let mut tools = list_tools(&bin);
+        tools.sort();
+        assert_eq!(tools, vec!["java", "javac"]);
+
+        let shims = dir.join("shims");
+        fs::create_dir_all(&shims).unwrap();
+        write_shim(&shims, Path::new("/usr/local/bin/jsh"), "java").unwrap();
+        let content = fs::read_to_string(shims.join("java")).unwrap();
+        assert!(content.contains("exec '/usr/local/bin/jsh' exec java \"$@\""));
+        assert!(is_executable(&shims.join("java")));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,298 @@
+use crate::config::JdkInfo;
+use crate::downloader::traits::{JdkPackage, is_lts_version};
+use crate::error::{JdkError, Result};
+use std::fmt;
+
+/// A requested JDK: optional vendor plus a version requirement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JdkSpec {
+    pub vendor: Option<String>,
+    pub version: VersionReq,
+}
+
+/// Which versions a spec accepts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionReq {
+    /// "17", "17.0.9" or "17.0.9+9": versions starting with these parts
+    Prefix { parts: Vec<u32>, build: Option<u32> },
+    /// ">=17" or "17+"
+    AtLeast(Vec<u32>),
+    /// newest long-term support release
+    Lts,
+    /// newest release
+    Latest,
+}
+
+impl JdkSpec {
+    /// Parse "17", "17.0.9+9", ">=17", "17+", "lts", "latest", "1.8", "temurin@21",
+    /// "temurin-17.0.9" or "17.0.9-tem"
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        let invalid = || JdkError::InvalidVersion(input.to_string());
+
+        if let Some((vendor, version)) = input.split_once('@') {
+            if !is_vendor(vendor) {
+                return Err(invalid());
+            }
+            let version = parse_version_req(version).ok_or_else(invalid)?;
+            return Ok(Self { vendor: Some(normalize_vendor(vendor)), version });
+        }
+
+        if let Some(version) = parse_version_req(input) {
+            return Ok(Self { vendor: None, version });
+        }
+
+        // vendor-version (asdf/mise style), vendor builds are dropped like in sdkman ids
+        if let Some((vendor, version)) = input.split_once('-')
+            && is_vendor(vendor)
+            && let Some(version) = parse_version_req(version)
+                .map(openjdk_version)
+                .or_else(|| parse_sdkman_version(version))
+        {
+            return Ok(Self { vendor: Some(normalize_vendor(vendor)), version });
+        }
+
+        // version-vendor (sdkman style)
+        if let Some((version, vendor)) = input.rsplit_once('-')
+            && is_vendor(vendor)
+            && let Some(version) = parse_sdkman_version(version)
+        {
+            return Ok(Self { vendor: Some(normalize_vendor(vendor)), version });
+        }
+
+        Err(invalid())
+    }
+
+    /// The release a download source publishes as `vendor` `version`, vendor build numbers are dropped
+    /// ("corretto", "17.0.9.8.1" -> corretto@17.0.9)
+    pub fn release(vendor: &str, version: &str) -> Result<Self> {
+        let version = parse_version_req(version)
+            .map(openjdk_version)
+            .or_else(|| parse_sdkman_version(version))
+            .ok_or_else(|| JdkError::InvalidVersion(version.to_string()))?;
+        Ok(Self { vendor: Some(normalize_vendor(vendor)), version })
+    }
+
+    /// Check if a registered JDK satisfies this spec
+    pub fn matches(&self, info: &JdkInfo) -> bool {
+        if let Some(vendor) = &self.vendor
+            && normalize_vendor(&info.vendor_id()) != *vendor
+        {
+            return false;
+        }
+        let parts = info.version_parts();
+        let build = info.java_version.as_deref().and_then(build_number);
+        let lts = parts.first().is_some_and(|major| is_lts_version(*major));
+        self.version.matches(&parts, build, lts)
+    }
+
+    /// Check if a downloadable package satisfies the version part of this spec,
+    /// the vendor is already picked by the download source
+    pub fn matches_package(&self, package: &JdkPackage) -> bool {
+        let parts = parse_version_parts(&package.version).unwrap_or_default();
+        self.version.matches(&parts, build_number(&package.version), package.is_lts)
+    }
+
+    /// True for specs naming a patch release or build ("17.0.5", "17.0.5+8") rather than a major version
+    pub fn names_release(&self) -> bool {
+        matches!(&self.version, VersionReq::Prefix { parts, build } if parts.len() > 1 || build.is_some())
+    }
+
+    /// Major version when the spec names one, `>=`, `lts` and `latest` depend on what is available
+    pub fn major(&self) -> Option<u32> {
+        match &self.version {
+            VersionReq::Prefix { parts, .. } => parts.first().copied(),
+            _ => None,
+        }
+    }
+}
+
+impl VersionReq {
+    /// `build` is ignored when unknown, installed JDKs rarely report it
+    fn matches(&self, parts: &[u32], build: Option<u32>, lts: bool) -> bool {
+        match self {
+            VersionReq::Prefix { parts: prefix, build: wanted } => {
+                parts.starts_with(prefix) && (wanted.is_none() || build.is_none() || *wanted == build)
+            }
+            VersionReq::AtLeast(min) => parts >= min.as_slice(),
+            VersionReq::Lts => lts,
+            VersionReq::Latest => true,
+        }
+    }
+
+    /// `lts` and `latest` ask for the newest match rather than any match
+    pub fn prefers_newest(&self) -> bool {
+        matches!(self, VersionReq::Lts | VersionReq::Latest)
+    }
+}
+
+impl fmt::Display for JdkSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(vendor) = &self.vendor {
+            write!(f, "{}@", vendor)?;
+        }
+        let join = |parts: &[u32]| parts.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(".");
+        match &self.version {
+            VersionReq::Prefix { parts, build: Some(build) } => write!(f, "{}+{}", join(parts), build),
+            VersionReq::Prefix { parts, build: None } => write!(f, "{}", join(parts)),
+            VersionReq::AtLeast(parts) => write!(f, ">={}", join(parts)),
+            VersionReq::Lts => write!(f, "lts"),
+            VersionReq::Latest => write!(f, "latest"),
+        }
+    }
+}
+
+fn is_vendor(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+fn parse_version_req(version: &str) -> Option<VersionReq> {
+    match version.to_lowercase().as_str() {
+        "lts" => return Some(VersionReq::Lts),
+        "latest" => return Some(VersionReq::Latest),
+        _ => {}
+    }
+    if let Some(min) = version.strip_prefix(">=").or_else(|| version.strip_suffix('+')) {
+        return parse_version_parts(min.trim()).map(VersionReq::AtLeast);
+    }
+    let parts = parse_version_parts(version)?;
+    Some(VersionReq::Prefix { parts, build: build_number(version) })
+}
+
+/// Numeric version parts, "1.8" -> [8], "17.0.9+9" -> [17, 0, 9]
+fn parse_version_parts(version: &str) -> Option<Vec<u32>> {
+    let version = version.split('+').next()?;
+    if version.is_empty() {
+        return None;
+    }
+    let mut parts = version
+        .split(['.', '_'])
+        .map(|p| p.parse().ok())
+        .collect::<Option<Vec<u32>>>()?;
+    if parts.len() > 1 && parts[0] == 1 {
+        parts.remove(0);
+    }
+    Some(parts)
+}
+
+/// sdkman ids put vendor builds and qualifiers after the OpenJDK version,
+/// "17.0.9.8.1" (Corretto) and "17.0.9.fx" (Liberica) -> 17.0.9
+fn parse_sdkman_version(version: &str) -> Option<VersionReq> {
+    let parts: Vec<u32> = version.split('.').map_while(|p| p.parse().ok()).collect();
+    if parts.is_empty() {
+        return None;
+    }
+    Some(openjdk_version(VersionReq::Prefix { parts, build: None }))
+}
+
+/// Keep major.minor.security of a version prefix, "17.0.9.8.1" -> 17.0.9
+fn openjdk_version(version: VersionReq) -> VersionReq {
+    match version {
+        VersionReq::Prefix { mut parts, build } => {
+            parts.truncate(3);
+            VersionReq::Prefix { parts, build }
+        }
+        other => other,
+    }
+}
+
+/// Build number after "+", "17.0.9+9" -> 9
+fn build_number(version: &str) -> Option<u32> {
+    let (_, build) = version.split_once('+')?;
+    build.split(|c: char| !c.is_ascii_digit()).next()?.parse().ok()
+}
+
+/// Map vendor aliases (including sdkman identifiers) onto registry vendor ids
+pub fn normalize_vendor(vendor: &str) -> String {
+    match vendor.to_lowercase().as_str() {
+        "tem" | "adoptium" | "adoptopenjdk" | "adopt" => "temurin".to_string(),
+        "amzn" | "amazon" => "corretto".to_string(),
+        "librca" | "bellsoft" => "liberica".to_string(),
+        "ms" => "microsoft".to_string(),
+        "sapmchn" | "sap" => "sapmachine".to_string(),
+        "sem" | "ibm" => "semeru".to_string(),
+        "graalce" | "graal" => "graalvm".to_string(),
+        "open" => "openjdk".to_string(),
+        "azul" => "zulu".to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn jdk(vendor: &str, java_version: &str) -> JdkInfo {
+        JdkInfo {
+            path: PathBuf::from("/jdk"),
+            version: java_version.split('.').next().unwrap().to_string(),
+            vendor: Some(vendor.to_string()),
+            java_version: Some(java_version.to_string()),
+            arch: None,
+            native_image: false,
+            jvm_impl: None,
+            image_type: None,
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let prefix = |vendor: Option<&str>, parts: &[u32], build: Option<u32>| JdkSpec {
+            vendor: vendor.map(|v| v.to_string()),
+            version: VersionReq::Prefix { parts: parts.to_vec(), build },
+        };
+        let req = |version: &str| JdkSpec::parse(version).unwrap().version;
+        assert_eq!(JdkSpec::parse("17").unwrap(), prefix(None, &[17], None));
+        assert_eq!(JdkSpec::parse("17.0.9+9").unwrap(), prefix(None, &[17, 0, 9], Some(9)));
+        assert_eq!(JdkSpec::parse("1.8").unwrap(), prefix(None, &[8], None));
+        assert_eq!(JdkSpec::parse("temurin-17.0.9").unwrap(), prefix(Some("temurin"), &[17, 0, 9], None));
+        assert_eq!(JdkSpec::parse("temurin-17.0.9+9").unwrap(), prefix(Some("temurin"), &[17, 0, 9], Some(9)));
+        assert_eq!(JdkSpec::parse("corretto-17.0.9.8.1").unwrap(), prefix(Some("corretto"), &[17, 0, 9], None));
+        assert_eq!(JdkSpec::parse("17.0.9-tem").unwrap(), prefix(Some("temurin"), &[17, 0, 9], None));
+        assert_eq!(JdkSpec::parse("17.0.9.8.1-amzn").unwrap(), prefix(Some("corretto"), &[17, 0, 9], None));
+        assert_eq!(JdkSpec::parse("17.0.9.fx-librca").unwrap(), prefix(Some("liberica"), &[17, 0, 9], None));
+        assert_eq!(JdkSpec::parse("21.fx-zulu").unwrap(), prefix(Some("zulu"), &[21], None));
+        assert_eq!(JdkSpec::parse("temurin@21").unwrap(), prefix(Some("temurin"), &[21], None));
+        assert_eq!(req(">=17"), VersionReq::AtLeast(vec![17]));
+        assert_eq!(req("17+"), VersionReq::AtLeast(vec![17]));
+        assert_eq!(req("LTS"), VersionReq::Lts);
+        assert_eq!(JdkSpec::parse("zulu@latest").unwrap().version, VersionReq::Latest);
+        assert_eq!(JdkSpec::parse("amzn@>=11.0.2").unwrap().to_string(), "corretto@>=11.0.2");
+        assert_eq!(JdkSpec::release("corretto", "17.0.9.8.1").unwrap().to_string(), "corretto@17.0.9");
+        assert_eq!(JdkSpec::release("temurin", "21.0.1+12.0.LTS").unwrap().to_string(), "temurin@21.0.1+12");
+        assert!(JdkSpec::parse("latest-and-greatest").is_err());
+        assert!(JdkSpec::parse("@17").is_err());
+        assert!(JdkSpec::parse("").is_err());
+
+        assert!(JdkSpec::parse("17.0.5+8").unwrap().names_release());
+        assert!(JdkSpec::parse("temurin@17.0.5").unwrap().names_release());
+        assert!(!JdkSpec::parse("17").unwrap().names_release());
+        assert!(!JdkSpec::parse(">=17.0.5").unwrap().names_release());
+    }
+
+    #[test]
+    fn test_matches() {
+        let temurin = jdk("Eclipse Temurin", "17.0.9");
+        assert!(JdkSpec::parse("17").unwrap().matches(&temurin));
+        assert!(JdkSpec::parse("17.0.9").unwrap().matches(&temurin));
+        assert!(JdkSpec::parse("17.0.9+9").unwrap().matches(&temurin));
+        assert!(JdkSpec::parse("17.0.9-tem").unwrap().matches(&temurin));
+        assert!(JdkSpec::parse("temurin@17").unwrap().matches(&temurin));
+        assert!(JdkSpec::parse("17.0.9.8.1-amzn").unwrap().matches(&jdk("Amazon Corretto", "17.0.9")));
+        assert!(!JdkSpec::parse("17.0.9.8.1-amzn").unwrap().matches(&jdk("Amazon Corretto", "17.0.8")));
+        assert!(JdkSpec::parse("corretto-17.0.9.8.1").unwrap().matches(&jdk("Amazon Corretto", "17.0.9")));
+        assert!(JdkSpec::parse("17.0.9.fx-librca").unwrap().matches(&jdk("BellSoft Liberica", "17.0.9")));
+        assert!(!JdkSpec::parse("17.0.2").unwrap().matches(&temurin));
+        assert!(!JdkSpec::parse("zulu-17").unwrap().matches(&temurin));
+        assert!(!JdkSpec::parse("1").unwrap().matches(&jdk("Oracle", "11.0.8")));
+        assert!(JdkSpec::parse("1.8").unwrap().matches(&jdk("Oracle", "1.8.0_291")));
+
+        assert!(JdkSpec::parse(">=17").unwrap().matches(&temurin));
+        assert!(JdkSpec::parse("11+").unwrap().matches(&temurin));
+        assert!(!JdkSpec::parse(">=17.0.10").unwrap().matches(&temurin));
+        assert!(JdkSpec::parse("lts").unwrap().matches(&temurin));
+        assert!(!JdkSpec::parse("lts").unwrap().matches(&jdk("Oracle", "22.0.1")));
+        assert!(JdkSpec::parse("latest").unwrap().matches(&jdk("Oracle", "22.0.1")));
+    }
+}
@@ -49,11 +49,48 @@ async fn run() -> error::Result<()> {
         Commands::Use { version } => {
             commands::use_command(&version)?;
         }
-        Commands::Download { version, vendor } => {
-            commands::download_command(&version, &vendor).await?;
+        Commands::Check { spec } => {
+            commands::check_command(&spec)?;
         }
-        Commands::Search { keyword } => {
-            commands::search_command(keyword).await?;
+        Commands::Local { version, unset } => {
+            commands::local_command(version.as_deref(), unset)?;
+        }
+        Commands::Env { version, shell, restore } => {
+            if restore {
+                commands::restore_command()?;
+            } else {
+                commands::env_command(shell, version.as_deref())?;
+            }
+        }
+        Commands::Init { shell } => {
+            commands::init_command(shell)?;
+        }
+        Commands::Rehash => {
+            commands::rehash_command()?;
+        }
+        Commands::Exec { tool, args } => {
+            commands::exec_command(&tool, &args)?;
+        }
+        Commands::Download { version, vendor, bundle, image, os, arch, dest, refresh } => {
+            downloader::mirror::set_mirrors(config::Config::load()?.mirrors);
+            downloader::cache::set_refresh(refresh);
+            downloader::traits::set_target(os.as_deref(), arch.as_deref())?;
+            commands::download_command(&version, vendor.as_deref(), bundle, image, dest.as_deref()).await?;
+        }
+        Commands::Install { from_file, sha256 } => {
+            commands::install_command(&from_file, sha256.as_deref())?;
+        }
+        Commands::Add { path } => {
+            commands::add_command(&path)?;
+        }
+        Commands::Remove { version, force } => {
+            commands::remove_command(&version, force)?;
+        }
+        Commands::Search { keyword, all, vendor, bundle, os, arch, refresh } => {
+            downloader::mirror::set_mirrors(config::Config::load()?.mirrors);
+            downloader::cache::set_refresh(refresh);
+            downloader::traits::set_target(os.as_deref(), arch.as_deref())?;
+            commands::search_command(keyword, vendor.as_deref(), bundle, all).await?;
         }
     }
     Ok(())